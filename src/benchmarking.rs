@@ -4,93 +4,852 @@ use super::*;
 
 use crate::{
     mock_const::*,
-    types::{BalanceOf, MorConfig},
+    mor::{MachineIdentityProvider, MorBalance},
+    offchain::KEY_TYPE,
+    types::{
+        AssetConfig, AssetIdOf, BalanceOf, CampaignEligibility, CampaignId, EligibilityRule,
+        EligibilityRules, Evidence, Heartbeat, MachineMetadata, MachineTariff, MorConfig, Offence,
+        StakeCurve, StakingConfig, TariffCurrency, MAX_ELIGIBILITY_RULES, MAX_EVIDENCE_LEN,
+    },
     Pallet as PeaqMor,
 };
-use frame_benchmarking::v1::{account, benchmarks, impl_benchmark_test_suite};
+use frame_benchmarking::v2::*;
+use frame_support::{
+    traits::{
+        fungible,
+        fungibles::{Create, Inspect, Mutate},
+        EnsureOrigin, Get, UnfilteredDispatchable,
+    },
+    BoundedVec,
+};
 use frame_system::{Pallet as System, RawOrigin};
-use peaq_pallet_did::Pallet as PeaqDid;
-use sp_runtime::traits::Zero;
+use parity_scale_codec::Encode;
+use sp_core::sr25519;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+    traits::{AccountIdConversion, IdentifyAccount, One, ValidateUnsigned, Zero},
+    transaction_validity::TransactionSource,
+    FixedU128, Perbill,
+};
+use sp_std::{vec, vec::Vec};
+
+/// Upper bound of the tracked block-rewards, see `MorConfig::track_n_block_rewards`.
+const MAX_TRACK_N: u32 = u8::MAX as u32;
 
 /// Assert that the last event equals the provided one.
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     System::<T>::assert_last_event(generic_event.into());
 }
 
-benchmarks! {
-    where_clause { where
-        BalanceOf<T>: From<u128> + Zero
-    }
+#[benchmarks(where
+    BalanceOf<T>: From<u128> + Zero,
+    AssetIdOf<T>: From<u32>,
+    T::Assets: Create<T::AccountId>,
+    T::MachinePublic: From<sr25519::Public>,
+    T::MachineSignature: From<sr25519::Signature>
+)]
+mod benchmarks {
+    use super::*;
 
-    get_registration_reward {
+    // The machine has to fulfil the largest set of eligibility rules possible.
+    #[benchmark]
+    fn get_registration_reward() {
         let owner: T::AccountId = account(O_ACCT, 0, 0);
         let machine: T::AccountId = account(M_ACCT, 0, 0);
-        PeaqDid::<T>::add_attribute(
+        add_did_attribute::<T>(&owner, &machine);
+        set_max_eligibility_rules::<T>(&owner, &machine);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), machine.clone());
+
+        assert_last_event::<T>(
+            Event::<T>::MachineRegistered {
+                owner,
+                machine,
+                reward: BalanceOf::<T>::from(REG_FEE),
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn get_online_rewards() {
+        let (owner, machine) = register_machine::<T>();
+        set_max_eligibility_rules::<T>(&owner, &machine);
+        bond_machine::<T>(&owner, &machine);
+        fund_pot::<T>(BalanceOf::<T>::from(REG_FEE * 10));
+        PeaqMor::<T>::log_block_rewards(BalanceOf::<T>::from(REG_FEE));
+        let config = def_staking_config::<T>();
+        let reward = config.base_reward(PeaqMor::<T>::period_reward_of());
+        let reward = reward + config.bonus(reward, BalanceOf::<T>::from(REG_FEE / 2));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), machine.clone());
+
+        assert!(!reward.is_zero());
+        assert_last_event::<T>(
+            Event::<T>::OnlineRewardPaid {
+                owner,
+                machine,
+                amount: reward,
+                period: PeaqMor::<T>::current_period(),
+            }
+            .into(),
+        );
+    }
+
+    // Like get_online_rewards, but the reward will be converted into the reward asset and
+    // paid out of the pot's asset balance.
+    #[benchmark]
+    fn get_online_rewards_in_asset() {
+        let (owner, machine) = register_machine::<T>();
+        set_max_eligibility_rules::<T>(&owner, &machine);
+        bond_machine::<T>(&owner, &machine);
+        let asset = accept_asset::<T>();
+        let pot: T::AccountId = T::PotId::get().into_account_truncating();
+        T::Assets::mint_into(asset.clone(), &pot, BalanceOf::<T>::from(REG_FEE * 10))
+            .expect("check unit-tests");
+        PeaqMor::<T>::set_reward_asset(RawOrigin::Root.into(), Some(asset))
+            .expect("check unit-tests");
+        PeaqMor::<T>::log_block_rewards(BalanceOf::<T>::from(REG_FEE));
+        let config = def_staking_config::<T>();
+        let reward = config.base_reward(PeaqMor::<T>::period_reward_of());
+        let reward = reward + config.bonus(reward, BalanceOf::<T>::from(REG_FEE / 2));
+
+        #[extrinsic_call]
+        get_online_rewards(RawOrigin::Signed(owner.clone()), machine.clone());
+
+        assert!(!reward.is_zero());
+        assert_last_event::<T>(
+            Event::<T>::OnlineRewardPaid {
+                owner,
+                machine,
+                amount: reward,
+                period: PeaqMor::<T>::current_period(),
+            }
+            .into(),
+        );
+    }
+
+    // The machine is registered, so the statistics of the machine and its owner will be
+    // updated too.
+    #[benchmark]
+    fn pay_machine_usage() {
+        let user: T::AccountId = account(U_ACCT, 0, 0);
+        let (_, machine) = register_machine::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(user.clone()),
+            machine.clone(),
+            BalanceOf::<T>::from(REG_FEE),
+        );
+
+        assert_last_event::<T>(
+            Event::<T>::UsagePaid {
+                payer: user,
+                machine,
+                amount: BalanceOf::<T>::from(REG_FEE),
+                fee: BalanceOf::<T>::zero(),
+            }
+            .into(),
+        );
+    }
+
+    // The resizing of the tracked block-rewards is linear in the new and the current size,
+    // so the current size will be the largest one possible.
+    #[benchmark]
+    fn set_configuration(t: Linear<1, 255>) {
+        let current = if t < MAX_TRACK_N { MAX_TRACK_N } else { 1 };
+        PeaqMor::<T>::set_configuration(RawOrigin::Root.into(), def_config::<T>(current as u8))
+            .expect("check unit-tests");
+        let old = PeaqMor::<T>::mor_config_of();
+        let config = def_config::<T>(t as u8);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, config.clone());
+
+        assert_last_event::<T>(Event::<T>::ConfigUpdated { old, new: config }.into());
+    }
+
+    #[benchmark]
+    fn fetch_pot_balance() {
+        #[extrinsic_call]
+        _(RawOrigin::Root);
+    }
+
+    #[benchmark]
+    fn set_machine_tariff() {
+        let (owner, machine) = register_machine::<T>();
+        let tariff = Some(MachineTariff::<BalanceOf<T>, AssetIdOf<T>> {
+            flat_fee: BalanceOf::<T>::from(REG_FEE),
+            unit_price: BalanceOf::<T>::from(REG_FEE),
+            currency: TariffCurrency::Native,
+        });
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), machine.clone(), tariff.clone());
+
+        assert_last_event::<T>(Event::<T>::MachineTariffChanged { machine, tariff }.into());
+    }
+
+    #[benchmark]
+    fn set_accepted_asset() {
+        let asset = create_asset::<T>();
+        let config = Some(AssetConfig::<BalanceOf<T>> {
+            machine_usage_fee_min: BalanceOf::<T>::from(ASSET_MIN_BALANCE),
+            machine_usage_fee_max: BalanceOf::<T>::from(REG_FEE),
+            reward_rate: FixedU128::one(),
+        });
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, asset.clone(), config.clone());
+
+        assert_last_event::<T>(Event::<T>::AcceptedAssetChanged { asset, config }.into());
+    }
+
+    #[benchmark]
+    fn pay_machine_usage_in_asset() {
+        let user: T::AccountId = account(U_ACCT, 0, 0);
+        let (_, machine) = register_machine::<T>();
+        let asset = accept_asset::<T>();
+        let amount = BalanceOf::<T>::from(REG_FEE);
+        T::Assets::mint_into(asset.clone(), &user, amount + amount).expect("check unit-tests");
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(user.clone()),
+            machine.clone(),
+            asset.clone(),
+            amount,
+        );
+
+        assert_last_event::<T>(
+            Event::<T>::UsagePaidInAsset {
+                payer: user,
+                machine,
+                asset,
+                amount,
+                fee: BalanceOf::<T>::zero(),
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn set_reward_asset() {
+        let asset = accept_asset::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(asset.clone()));
+
+        assert_last_event::<T>(Event::<T>::RewardAssetChanged { asset: Some(asset) }.into());
+    }
+
+    #[benchmark]
+    fn withdraw_from_pot() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let treasury: T::AccountId = account("Treasury", 0, 0);
+        let amount = BalanceOf::<T>::from(REG_FEE);
+        fund_pot::<T>(amount + amount);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, treasury.clone(), amount);
+
+        assert_last_event::<T>(
+            Event::<T>::PotWithdrawn {
+                destination: treasury,
+                amount,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn donate_to_pot() {
+        let user: T::AccountId = account(U_ACCT, 0, 0);
+        let amount = BalanceOf::<T>::from(REG_FEE);
+        <T::Currency as fungible::Mutate<T::AccountId>>::mint_into(&user, amount + amount)
+            .expect("check unit-tests");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(user.clone()), amount);
+
+        assert_last_event::<T>(
+            Event::<T>::PotDonated {
+                donor: user,
+                amount,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn sweep_pot() {
+        fund_pot::<T>(T::PotCeiling::get() + BalanceOf::<T>::from(REG_FEE));
+
+        #[block]
+        {
+            PeaqMor::<T>::sweep_pot();
+        }
+
+        let pot: T::AccountId = T::PotId::get().into_account_truncating();
+        assert!(
+            <T::Currency as fungible::Inspect<T::AccountId>>::balance(&pot) <= T::PotCeiling::get()
+        );
+    }
+
+    #[benchmark]
+    fn log_block_rewards(t: Linear<1, 255>) {
+        PeaqMor::<T>::set_configuration(RawOrigin::Root.into(), def_config::<T>(t as u8))
+            .expect("check unit-tests");
+        let amount = BalanceOf::<T>::from(REG_FEE);
+
+        #[block]
+        {
+            PeaqMor::<T>::log_block_rewards(amount);
+        }
+
+        assert_eq!(PeaqMor::<T>::period_reward_of(), amount);
+    }
+
+    #[benchmark]
+    fn create_campaign() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let budget = BalanceOf::<T>::from(REG_FEE * 10);
+        fund_pot::<T>(budget + budget);
+        let start = System::<T>::block_number();
+        let eligibility = CampaignEligibility::MachineClass(
+            M_ATTR.to_vec().try_into().unwrap(),
+            M_VAL.to_vec().try_into().unwrap(),
+        );
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            CAMPAIGN_NAME.to_vec().try_into().unwrap(),
+            budget,
+            BalanceOf::<T>::from(REG_FEE),
+            1u32.into(),
+            start,
+            start + 100u32.into(),
+            eligibility,
+        );
+
+        assert_last_event::<T>(
+            Event::<T>::CampaignCreated {
+                campaign_id: 0,
+                pot: PeaqMor::<T>::campaign_account(0),
+                budget,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn claim_campaign_reward() {
+        let (owner, machine) = register_machine::<T>();
+        let campaign_id = start_campaign::<T>();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(owner.clone()),
+            campaign_id,
+            machine.clone(),
+        );
+
+        assert_last_event::<T>(
+            Event::<T>::CampaignRewardPaid {
+                campaign_id,
+                owner,
+                machine,
+                amount: BalanceOf::<T>::from(REG_FEE),
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn close_campaign() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let campaign_id = start_campaign::<T>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, campaign_id);
+
+        assert_last_event::<T>(
+            Event::<T>::CampaignClosed {
+                campaign_id,
+                returned: BalanceOf::<T>::from(REG_FEE * 10),
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_machine_metadata() {
+        let (owner, machine) = register_machine::<T>();
+        let metadata = Some(def_metadata());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), machine.clone(), metadata.clone());
+
+        assert_last_event::<T>(Event::<T>::MachineMetadataChanged { machine, metadata }.into());
+    }
+
+    #[benchmark]
+    fn set_eligibility_rules() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let rules = max_eligibility_rules();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, rules.clone());
+
+        assert_last_event::<T>(Event::<T>::EligibilityRulesChanged { rules }.into());
+        Ok(())
+    }
+
+    // The registration will be removed and the bounty will be paid out of the pot. In the
+    // worst case the former owner has published a tariff and metadata and bonded tokens,
+    // and the machine has attestations, a pending report and a full offence history, which
+    // all have to be removed as well.
+    #[benchmark]
+    fn reap_stale_machine(
+        a: Linear<0, { T::MaxAttestations::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let (owner, machine) = register_machine::<T>();
+        let machine_hash = machine.using_encoded(blake2_256);
+        PeaqMor::<T>::set_machine_tariff(
             RawOrigin::Signed(owner.clone()).into(),
             machine.clone(),
-            M_ATTR.to_vec(),
-            M_VAL.to_vec(),
-            None
-        ).expect("check unit-tests");
-    }: _(RawOrigin::Signed(owner.clone()), machine.clone())
-    verify {
-        assert_last_event::<T>(Event::<T>::RegistrationRewardPayed(
-            owner.clone(), BalanceOf::<T>::from(REG_FEE)
-        ).into());
-    }
-
-    get_online_rewards {
-        let owner: T::AccountId = account(O_ACCT, 0, 0);
-        let machine: T::AccountId = account(M_ACCT, 0, 0);
-        PeaqDid::<T>::add_attribute(
+            Some(MachineTariff::<BalanceOf<T>, AssetIdOf<T>> {
+                flat_fee: BalanceOf::<T>::from(REG_FEE),
+                unit_price: BalanceOf::<T>::from(REG_FEE),
+                currency: TariffCurrency::Native,
+            }),
+        )
+        .expect("check unit-tests");
+        PeaqMor::<T>::set_machine_metadata(
             RawOrigin::Signed(owner.clone()).into(),
             machine.clone(),
-            M_ATTR.to_vec(),
-            M_VAL.to_vec(),
-            None
-        ).expect("check unit-tests");
-        PeaqMor::<T>::get_registration_reward(
+            Some(def_metadata()),
+        )
+        .expect("check unit-tests");
+        bond_machine::<T>(&owner, &machine);
+
+        let period = PeaqMor::<T>::current_period();
+        for i in 0..a {
+            let reporter: T::AccountId = account("Reporter", i, 0);
+            LivenessAttestations::<T>::insert(machine_hash, reporter, (period, false));
+        }
+        let reporter =
+            T::ReporterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        PeaqMor::<T>::report_misbehaviour(reporter, machine.clone(), max_evidence())
+            .expect("check unit-tests");
+        let offence = Offence {
+            reported_at: System::<T>::block_number(),
+            slashed: BalanceOf::<T>::zero(),
+            frozen_until: System::<T>::block_number(),
+        };
+        OffenceHistory::<T>::insert(
+            machine_hash,
+            BoundedVec::truncate_from(vec![offence; T::MaxOffences::get() as usize]),
+        );
+
+        let stale_owner: T::AccountId = account(U_ACCT, 0, 0);
+        MachineRegister::<T>::insert(machine_hash, stale_owner.using_encoded(blake2_256));
+        MachineAccounts::<T>::insert(machine_hash, (machine.clone(), stale_owner));
+        fund_pot::<T>(BalanceOf::<T>::from(REG_FEE * 10));
+        let reaper: T::AccountId = account("Reaper", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(reaper.clone()), machine.clone());
+
+        assert_last_event::<T>(
+            Event::<T>::StaleMachineReaped {
+                machine,
+                reaper,
+                bounty: T::ReapBounty::get(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            LivenessAttestations::<T>::iter_prefix(machine_hash).count(),
+            0
+        );
+        assert!(MachineBonds::<T>::get(machine_hash).is_none());
+        assert!(PeaqMor::<T>::offences_of(machine_hash).is_empty());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn bond() {
+        let (owner, machine) = register_machine::<T>();
+        let amount = BalanceOf::<T>::from(REG_FEE / 2);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), machine.clone(), amount);
+
+        assert_last_event::<T>(Event::<T>::Bonded { machine, amount }.into());
+    }
+
+    #[benchmark]
+    fn unbond() {
+        let (owner, machine) = register_machine::<T>();
+        bond_machine::<T>(&owner, &machine);
+        let amount = BalanceOf::<T>::from(REG_FEE / 4);
+        let unlock_at = System::<T>::block_number() + 10u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), machine.clone(), amount);
+
+        assert_last_event::<T>(
+            Event::<T>::Unbonded {
+                machine,
+                amount,
+                unlock_at,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn withdraw_unbonded() {
+        let (owner, machine) = register_machine::<T>();
+        bond_machine::<T>(&owner, &machine);
+        let amount = BalanceOf::<T>::from(REG_FEE / 4);
+        PeaqMor::<T>::unbond(
             RawOrigin::Signed(owner.clone()).into(),
-            machine.clone()
-        ).expect("check unit-tests");
-    }: _(RawOrigin::Signed(owner.clone()), machine.clone())
-    verify {
-        assert_last_event::<T>(Event::<T>::OnlineRewardsPayed(
-            owner.clone(), BalanceOf::<T>::zero()
-        ).into());
+            machine.clone(),
+            amount,
+        )
+        .expect("check unit-tests");
+        System::<T>::set_block_number(System::<T>::block_number() + 10u32.into());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), machine.clone());
+
+        assert_last_event::<T>(Event::<T>::BondWithdrawn { machine, amount }.into());
     }
 
-    pay_machine_usage {
-        let user: T::AccountId = account(U_ACCT, 0, 0);
-        let machine: T::AccountId = account(M_ACCT, 0, 0);
-    }: _(RawOrigin::Signed(user.clone()), machine.clone(), BalanceOf::<T>::from(REG_FEE))
-    verify {
-        assert_last_event::<T>(Event::<T>::MachineUsagePayed(
-            machine, BalanceOf::<T>::from(REG_FEE)
-        ).into());
-    }
-
-    set_configuration {
-        let config: MorConfig<BalanceOf<T>> = MorConfig::<BalanceOf<T>>{
-            registration_reward: BalanceOf::<T>::from(REG_FEE),
-            machine_usage_fee_min: BalanceOf::<T>::from(100_000_000_000_000_000u128),
-            machine_usage_fee_max: BalanceOf::<T>::from(3_000_000_000_000_000_000u128),
-            track_n_block_rewards: 10u8
+    #[benchmark]
+    fn set_staking_config() -> Result<(), BenchmarkError> {
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let config = def_staking_config::<T>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, config.clone());
+
+        assert_last_event::<T>(Event::<T>::StakingConfigChanged { config }.into());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn report_misbehaviour() -> Result<(), BenchmarkError> {
+        let (_, machine) = register_machine::<T>();
+        let origin =
+            T::ReporterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let reporter = T::ReporterOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, machine.clone(), max_evidence());
+
+        assert_last_event::<T>(Event::<T>::MisbehaviourReported { machine, reporter }.into());
+        Ok(())
+    }
+
+    // The offence will be confirmed, the bond slashed and the full history rotated.
+    #[benchmark]
+    fn adjudicate_misbehaviour() -> Result<(), BenchmarkError> {
+        let (owner, machine) = register_machine::<T>();
+        bond_machine::<T>(&owner, &machine);
+        let reporter =
+            T::ReporterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        PeaqMor::<T>::report_misbehaviour(reporter, machine.clone(), max_evidence())
+            .expect("check unit-tests");
+        let offence = Offence {
+            reported_at: System::<T>::block_number(),
+            slashed: BalanceOf::<T>::zero(),
+            frozen_until: System::<T>::block_number(),
         };
-    }: _(RawOrigin::Root, config.clone())
-    verify {
-        assert_last_event::<T>(Event::<T>::MorConfigChanged(
-            config
-        ).into());
+        OffenceHistory::<T>::insert(
+            machine.using_encoded(blake2_256),
+            BoundedVec::truncate_from(vec![offence; T::MaxOffences::get() as usize]),
+        );
+        let origin =
+            T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, machine.clone(), true);
+
+        assert_eq!(
+            PeaqMor::<T>::offences_of(machine.using_encoded(blake2_256)).len() as u32,
+            T::MaxOffences::get()
+        );
+        Ok(())
     }
 
-    fetch_pot_balance {
-    }: _(RawOrigin::Root)
-    verify {
-        // assert_last_event::<T>(Event::<T>::FetchedPotBalance(
-        //     BalanceOf::<T>::from(10_000_000_000_000_000_000u128)
-        // ).into());
+    // Each attested machine is registered.
+    #[benchmark]
+    fn submit_liveness(a: Linear<1, { T::MaxAttestations::get() }>) -> Result<(), BenchmarkError> {
+        let owner: T::AccountId = account(O_ACCT, 0, 0);
+        let machines: Vec<(T::AccountId, bool)> = (0..a)
+            .map(|i| {
+                let machine: T::AccountId = account(M_ACCT, i, 0);
+                MachineRegister::<T>::insert(
+                    machine.using_encoded(blake2_256),
+                    owner.using_encoded(blake2_256),
+                );
+                (machine, false)
+            })
+            .collect();
+        let origin =
+            T::ReporterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let reporter = T::ReporterOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let attestations: BoundedVec<_, T::MaxAttestations> = machines
+            .try_into()
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, attestations);
+
+        assert_last_event::<T>(Event::<T>::LivenessAttested { reporter, count: a }.into());
+        Ok(())
+    }
+
+    // The heartbeat will be validated, including its signature, before it will be
+    // dispatched, so both are measured. The machine's key lives in the keystore.
+    #[benchmark]
+    fn submit_heartbeat() -> Result<(), BenchmarkError> {
+        let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+        let machine = T::MachinePublic::from(public).into_account();
+        register_machine_as::<T>(&machine);
+        let heartbeat = Heartbeat {
+            machine: machine.clone(),
+            period: PeaqMor::<T>::current_period(),
+            nonce: u32::MAX,
+        };
+        let payload = PeaqMor::<T>::heartbeat_payload(&heartbeat);
+        let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &public, &payload)
+            .ok_or(BenchmarkError::Weightless)?;
+        let call = Call::<T>::submit_heartbeat {
+            heartbeat: heartbeat.clone(),
+            _signature: signature.into(),
+        };
+
+        #[block]
+        {
+            PeaqMor::<T>::validate_unsigned(TransactionSource::External, &call)
+                .map_err(<&str>::from)?;
+            call.dispatch_bypass_filter(RawOrigin::None.into())?;
+        }
+
+        assert_last_event::<T>(
+            Event::<T>::HeartbeatReceived {
+                machine,
+                period: heartbeat.period,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(PeaqMor, crate::mock::new_test_ext(), crate::mock::Test);
+}
+
+/// Returns a valid configuration, which tracks the given number of block-rewards.
+fn def_config<T: Config>(track_n_block_rewards: u8) -> MorConfig<BalanceOf<T>>
+where
+    BalanceOf<T>: From<u128> + Zero,
+{
+    MorConfig::<BalanceOf<T>> {
+        registration_reward: BalanceOf::<T>::from(REG_FEE),
+        machine_usage_fee_min: BalanceOf::<T>::from(100_000_000_000_000_000u128),
+        machine_usage_fee_max: BalanceOf::<T>::from(3_000_000_000_000_000_000u128),
+        track_n_block_rewards,
+        max_mint_per_period: BalanceOf::<T>::from(MINT_CAP),
+        mint_period: 10u32,
+        emission_budget: BalanceOf::<T>::from(EMISSION_BUDGET),
+        pot_reserve: BalanceOf::<T>::zero(),
+        pot_low_threshold: BalanceOf::<T>::zero(),
     }
 }
 
-impl_benchmark_test_suite!(PeaqMor, crate::mock::new_test_ext(), crate::mock::Test);
+/// Returns metadata of the generic machine, which refers to its generic attribute.
+fn def_metadata() -> MachineMetadata {
+    MachineMetadata {
+        machine_type: M_VAL.to_vec().try_into().unwrap(),
+        geohash: M_GEOHASH.to_vec().try_into().unwrap(),
+        capacity: 22,
+        firmware: M_FIRMWARE.to_vec().try_into().unwrap(),
+        did_attribute: M_ATTR.to_vec().try_into().unwrap(),
+        endpoint: M_ENDPOINT.to_vec().try_into().unwrap(),
+    }
+}
+
+/// Adds the generic attribute of the machine to its identity, e.g. in Peaq-DID.
+fn add_did_attribute<T: Config>(owner: &T::AccountId, machine: &T::AccountId) {
+    T::Identity::add_attribute(owner, machine, M_ATTR, M_VAL);
+}
+
+/// Returns evidence of a misbehaviour with the maximum length.
+fn max_evidence() -> Evidence {
+    BoundedVec::truncate_from(vec![0xeeu8; MAX_EVIDENCE_LEN as usize])
+}
+
+/// Returns the largest set of eligibility rules, each one requires another attribute.
+fn max_eligibility_rules() -> EligibilityRules {
+    let rules: Vec<EligibilityRule> = (0..MAX_ELIGIBILITY_RULES as u8)
+        .map(|i| EligibilityRule {
+            name: [M_ATTR, &[i]].concat().try_into().unwrap(),
+            value: Some(M_VAL.to_vec().try_into().unwrap()),
+        })
+        .collect();
+    rules.try_into().unwrap()
+}
+
+/// Adds the attributes, which are required by the largest set of eligibility rules, to
+/// the machine's identity and configures these rules in Peaq-MOR.
+fn set_max_eligibility_rules<T: Config>(owner: &T::AccountId, machine: &T::AccountId) {
+    let rules = max_eligibility_rules();
+    for rule in rules.iter() {
+        T::Identity::add_attribute(owner, machine, &rule.name, M_VAL);
+    }
+    PeaqMor::<T>::set_eligibility_rules(
+        T::AdminOrigin::try_successful_origin().expect("check unit-tests"),
+        rules,
+    )
+    .expect("check unit-tests");
+}
+
+/// Returns a staking configuration, which gives a bonus for bonds up to the registration
+/// reward.
+fn def_staking_config<T: Config>() -> StakingConfig<BalanceOf<T>, T::BlockNumber>
+where
+    BalanceOf<T>: From<u128>,
+{
+    StakingConfig {
+        curve: StakeCurve::Sqrt,
+        full_stake: BalanceOf::<T>::from(REG_FEE),
+        max_bonus: Perbill::from_percent(50),
+        unbonding_period: 10u32.into(),
+    }
+}
+
+/// Configures staking and bonds half of the registration reward behind the machine.
+fn bond_machine<T: Config>(owner: &T::AccountId, machine: &T::AccountId)
+where
+    BalanceOf<T>: From<u128>,
+{
+    PeaqMor::<T>::set_staking_config(
+        T::AdminOrigin::try_successful_origin().expect("check unit-tests"),
+        def_staking_config::<T>(),
+    )
+    .expect("check unit-tests");
+    PeaqMor::<T>::bond(
+        RawOrigin::Signed(owner.clone()).into(),
+        machine.clone(),
+        BalanceOf::<T>::from(REG_FEE / 2),
+    )
+    .expect("check unit-tests");
+}
+
+/// Registers the generic machine in Peaq-DID and Peaq-MOR.
+fn register_machine<T: Config>() -> (T::AccountId, T::AccountId) {
+    let machine: T::AccountId = account(M_ACCT, 0, 0);
+    (register_machine_as::<T>(&machine), machine)
+}
+
+/// Registers the given machine for the generic owner in Peaq-DID and Peaq-MOR.
+fn register_machine_as<T: Config>(machine: &T::AccountId) -> T::AccountId {
+    let owner: T::AccountId = account(O_ACCT, 0, 0);
+    add_did_attribute::<T>(&owner, machine);
+    PeaqMor::<T>::get_registration_reward(RawOrigin::Signed(owner.clone()).into(), machine.clone())
+        .expect("check unit-tests");
+    owner
+}
+
+/// Mints the given amount into the pot.
+fn fund_pot<T: Config>(amount: BalanceOf<T>) {
+    let pot: T::AccountId = T::PotId::get().into_account_truncating();
+    <T::Currency as fungible::Mutate<T::AccountId>>::mint_into(&pot, amount)
+        .expect("check unit-tests");
+}
+
+/// Funds the pot and creates a running campaign for machines of the generic class.
+fn start_campaign<T: Config>() -> CampaignId
+where
+    BalanceOf<T>: From<u128>,
+{
+    let budget = BalanceOf::<T>::from(REG_FEE * 10);
+    fund_pot::<T>(budget + budget);
+    let start = System::<T>::block_number();
+    let campaign_id = PeaqMor::<T>::next_campaign_id();
+    PeaqMor::<T>::create_campaign(
+        T::AdminOrigin::try_successful_origin().expect("check unit-tests"),
+        CAMPAIGN_NAME.to_vec().try_into().unwrap(),
+        budget,
+        BalanceOf::<T>::from(REG_FEE),
+        1u32.into(),
+        start,
+        start + 100u32.into(),
+        CampaignEligibility::MachineClass(
+            M_ATTR.to_vec().try_into().unwrap(),
+            M_VAL.to_vec().try_into().unwrap(),
+        ),
+    )
+    .expect("check unit-tests");
+    campaign_id
+}
+
+/// Creates the generic asset, if it does not exist yet.
+fn create_asset<T: Config>() -> AssetIdOf<T>
+where
+    BalanceOf<T>: From<u128>,
+    AssetIdOf<T>: From<u32>,
+    T::Assets: Create<T::AccountId>,
+{
+    let owner: T::AccountId = account(O_ACCT, 0, 0);
+    let asset = AssetIdOf::<T>::from(ASSET_ID);
+    if !T::Assets::asset_exists(asset.clone()) {
+        T::Assets::create(
+            asset.clone(),
+            owner,
+            true,
+            BalanceOf::<T>::from(ASSET_MIN_BALANCE),
+        )
+        .expect("check unit-tests");
+    }
+    asset
+}
+
+/// Creates the generic asset and whitelists it in Peaq-MOR.
+fn accept_asset<T: Config>() -> AssetIdOf<T>
+where
+    BalanceOf<T>: From<u128>,
+    AssetIdOf<T>: From<u32>,
+    T::Assets: Create<T::AccountId>,
+{
+    let asset = create_asset::<T>();
+    PeaqMor::<T>::set_accepted_asset(
+        RawOrigin::Root.into(),
+        asset.clone(),
+        Some(AssetConfig::<BalanceOf<T>> {
+            machine_usage_fee_min: BalanceOf::<T>::from(ASSET_MIN_BALANCE),
+            machine_usage_fee_max: BalanceOf::<T>::from(REG_FEE),
+            reward_rate: FixedU128::one(),
+        }),
+    )
+    .expect("check unit-tests");
+    asset
+}
@@ -1,7 +1,6 @@
 //! Encapsules all error types and relevant methods of this pallet.
 
 use parity_scale_codec::{Decode, Encode};
-use peaq_pallet_did::did::DidError;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +12,21 @@ pub type MorResult<T> = core::result::Result<T, MorError>;
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialEq, Eq, Encode, Decode)]
 pub enum MorError {
+    /// Sent when an asset is used for payments or rewards, which has not been
+    /// whitelisted in Peaq-MOR.
+    AssetNotAccepted,
+    /// Sent when an asset is selected for online rewards, which has no reward rate.
+    AssetWithoutRewardRate,
+    /// Sent when a reward campaign does not exist.
+    CampaignNotFound,
+    /// Sent when rewards of a campaign are claimed before its start or after its end.
+    CampaignNotActive,
+    /// Sent when a campaign is created with inconsistent parameters.
+    CampaignIsNotConsistent,
+    /// Sent when the budget of a campaign does not cover another claim.
+    CampaignBudgetExhausted,
+    /// Sent when a campaign's reward of a machine is claimed again within the claim interval.
+    CampaignRewardAlreadyClaimed,
     /// Sent when authorization fails in Peaq-DID, when registering the machine
     /// in Peaq-MOR, or when someone trys to get the online rewards for a machine,
     /// who does not own it.
@@ -23,28 +37,63 @@ pub enum MorError {
     MachineAlreadyRegistered,
     /// Sent when a machine is not registered in Peaq-MOR.
     MachineNotRegistered,
+    /// Sent when a machine does not fulfil the eligibility rules of a campaign, or the
+    /// eligibility rules for registration and online rewards.
+    MachineNotEligible,
+    /// Sent when a registration should be reaped, but it is still backed by Peaq-DID.
+    MachineNotStale,
+    /// Sent when a machine has no bond, or when its bond belongs to another owner.
+    MachineNotBonded,
+    /// Sent when more tokens should be unbonded than are bonded behind a machine.
+    InsufficientBond,
+    /// Sent when unbonded tokens should be withdrawn before the end of the unbonding period.
+    BondIsUnbonding,
+    /// Sent when a machine's misbehaviour is reported, but a report awaits adjudication.
+    ReportAlreadyPending,
+    /// Sent when there is no pending report of a machine's misbehaviour to adjudicate.
+    ReportNotFound,
+    /// Sent when online rewards are requested for a machine, whose rewards are frozen
+    /// because of a confirmed offence.
+    MachineRewardsFrozen,
+    /// Sent when online rewards are requested for a machine, which has been attested
+    /// offline within the current reward period.
+    MachineOffline,
+    /// Sent when a heartbeat has been signed for another than the current reward period.
+    HeartbeatOutdated,
+    /// Sent when a machine has already sent a heartbeat in the current reward period.
+    HeartbeatAlreadyReceived,
     /// Sent when the amount of a machine usage payment is out of the configured range.
     MachinePaymentOutOfRange,
+    /// Sent when the amount of a machine usage payment does not match the tariff,
+    /// which has been published by the machine's owner.
+    MachinePaymentDoesNotMatchTariff,
+    /// Sent when a machine's tariff is not within the configured range of usage fees.
+    MachineTariffOutOfRange,
+    /// Sent when a machine's metadata is not consistent, e.g. when its geohash is invalid.
+    MachineMetadataIsNotConsistent,
+    /// Sent when a machine's metadata refers to an attribute, which does not exist in
+    /// the machine's document in Peaq-DID.
+    DidAttributeNotFound,
     /// Sent when authorization fails in Peaq-MOR. This can happen, if the owner
-    /// of a machine gets updated in Peaq-DID, but not in Peaq-MOR.
+    /// of a machine gets updated in Peaq-DID, but not in Peaq-MOR (see `reap_stale_machine`).
     MorAuthorizationFailed,
     /// Sent when tried to update pallet's config, but failed because config
     /// is not consistent.
     MorConfigIsNotConsistent,
+    /// Sent when tokens should be minted, but the maximum amount of minted tokens
+    /// within the current minting period has been reached.
+    MintPeriodCapReached,
+    /// Sent when tokens should be minted, but the lifetime emission budget of
+    /// Peaq-MOR has been exhausted.
+    EmissionBudgetExhausted,
+    /// Sent when a donation to the pot is below the existential deposit.
+    DonationTooSmall,
     /// Sent when Tokens could not be transfered, e.g. when minted or taken from pot.
     TokensCouldNotBeTransfered,
-    /// Sent when an unexpected Peaq-DID error occurs. This means, return
-    /// to developer of the Peaq-MOR pallet.
+    /// Sent when an unexpected Peaq-DID error occurs. It is not sent anymore, since
+    /// identities are looked up via `MachineIdentityProvider`, but it is kept so the
+    /// indices of the errors do not change.
     UnexpectedDidError,
     /// Internal error happened
     UnknownError,
 }
-
-impl From<DidError> for MorError {
-    fn from(err: DidError) -> Self {
-        match err {
-            DidError::AuthorizationFailed => MorError::DidAuthorizationFailed,
-            _ => MorError::UnexpectedDidError,
-        }
-    }
-}
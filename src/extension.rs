@@ -0,0 +1,168 @@
+//! Signed extension, which waives the transaction fees of registered machines for
+//! whitelisted calls of this pallet (see `Config::MachineFeelessCalls`), so machines can
+//! run with an almost empty wallet. Each machine has a quota of fee-less transactions per
+//! reward period (see `Config::MachineFeeQuota`), further transactions will be charged.
+//!
+//! `CheckMorMachineFee` wraps the extension, which charges the fees in the runtime, e.g.
+//! `pallet_transaction_payment::ChargeTransactionPayment`, and replaces it in the runtime's
+//! `SignedExtra`:
+//!     ```ignore
+//!     pub type SignedExtra = (
+//!         // ...
+//!         peaq_pallet_mor::extension::CheckMorMachineFee<
+//!             Runtime,
+//!             pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+//!         >,
+//!     );
+//!     ```
+
+use frame_support::traits::{Contains, Get, IsSubType};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+    traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        TransactionSource, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    DispatchResult,
+};
+use sp_std::marker::PhantomData;
+
+use crate::{Call, Config, FeelessCallsUsed, MachineRegister, Pallet};
+
+/// Waives the transaction fees of registered machines for whitelisted calls within their
+/// quota, otherwise the wrapped extension `S` charges the fees.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckMorMachineFee<T: Config + Send + Sync, S>(pub S, PhantomData<T>);
+
+impl<T: Config + Send + Sync, S> CheckMorMachineFee<T, S> {
+    /// Wraps the extension, which charges the transaction fees.
+    pub fn new(charge: S) -> Self {
+        Self(charge, PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync, S: sp_std::fmt::Debug> sp_std::fmt::Debug
+    for CheckMorMachineFee<T, S>
+{
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckMorMachineFee({:?})", self.0)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config> Pallet<T>
+where
+    T::RuntimeCall: IsSubType<Call<T>>,
+{
+    /// Checks, whether the fees of the transaction will be waived, i.e. the call is
+    /// whitelisted, the signer is a registered machine and its quota of the current reward
+    /// period is not exhausted. Returns the machine's hash, the period and the number of
+    /// fee-less transactions in this period including this one.
+    pub(crate) fn machine_fee_waiver(
+        who: &T::AccountId,
+        call: &T::RuntimeCall,
+    ) -> Option<([u8; 32], u32, u32)> {
+        let mor_call = call.is_sub_type()?;
+        if !T::MachineFeelessCalls::contains(mor_call) {
+            return None;
+        }
+        let machine_hash = (who).using_encoded(blake2_256);
+        if !MachineRegister::<T>::contains_key(machine_hash) {
+            return None;
+        }
+
+        let period = Self::current_period();
+        let (last_period, used) = FeelessCallsUsed::<T>::get(machine_hash);
+        let used = if last_period == period { used } else { 0 };
+        if used >= T::MachineFeeQuota::get() {
+            return None;
+        }
+        Some((machine_hash, period, used + 1))
+    }
+}
+
+impl<T, S> SignedExtension for CheckMorMachineFee<T, S>
+where
+    T: Config + Send + Sync,
+    T::RuntimeCall: IsSubType<Call<T>>,
+    S: SignedExtension<AccountId = T::AccountId, Call = T::RuntimeCall>,
+{
+    const IDENTIFIER: &'static str = "CheckMorMachineFee";
+    type AccountId = T::AccountId;
+    type Call = T::RuntimeCall;
+    type AdditionalSigned = S::AdditionalSigned;
+    // `None`, if the fees have been waived.
+    type Pre = Option<S::Pre>;
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        self.0.additional_signed()
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        match Pallet::<T>::machine_fee_waiver(who, call) {
+            Some(_) => Ok(ValidTransaction::default()),
+            None => self.0.validate(who, call, info, len),
+        }
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        match Pallet::<T>::machine_fee_waiver(who, call) {
+            Some((machine_hash, period, used)) => {
+                FeelessCallsUsed::<T>::insert(machine_hash, (period, used));
+                Ok(None)
+            }
+            None => self.0.pre_dispatch(who, call, info, len).map(Some),
+        }
+    }
+
+    fn post_dispatch(
+        pre: Option<Self::Pre>,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        match pre {
+            // The fees have been waived
+            Some(None) => Ok(()),
+            Some(pre) => S::post_dispatch(pre, info, post_info, len, result),
+            None => S::post_dispatch(None, info, post_info, len, result),
+        }
+    }
+
+    fn validate_unsigned(
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        S::validate_unsigned(call, info, len)
+    }
+
+    fn pre_dispatch_unsigned(
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        S::pre_dispatch_unsigned(call, info, len)
+    }
+}
@@ -0,0 +1,124 @@
+//! Implementations of the `MachineIdentityProvider`, which tell this pallet, where
+//! machines and their owners are registered.
+
+use frame_support::storage::unhashed;
+use parity_scale_codec::{Decode, Encode};
+#[cfg(feature = "peaq-did")]
+use peaq_pallet_did::{did::Did, Pallet as DidPallet};
+use sp_std::marker::PhantomData;
+
+use crate::{mor::MachineIdentityProvider, types::MachineAttribute};
+
+/// Machine identities, which are registered in Peaq-DID.
+#[cfg(feature = "peaq-did")]
+pub struct PeaqDidIdentity<T>(PhantomData<T>);
+
+#[cfg(feature = "peaq-did")]
+impl<T> MachineIdentityProvider<T::AccountId> for PeaqDidIdentity<T>
+where
+    T: crate::Config + peaq_pallet_did::Config,
+{
+    type BlockNumber = T::BlockNumber;
+
+    fn is_owner(owner: &T::AccountId, machine: &T::AccountId) -> bool {
+        DidPallet::<T>::is_owner(owner, machine).is_ok()
+    }
+
+    // Peaq-DID keeps the owners of documents by owner and DID account, so it can confirm an
+    // owner, but it cannot look up the owner of a document.
+    fn owner_of(_machine: &T::AccountId) -> Option<T::AccountId> {
+        None
+    }
+
+    fn attribute_of(
+        machine: &T::AccountId,
+        name: &[u8],
+    ) -> Option<MachineAttribute<Self::BlockNumber>> {
+        DidPallet::<T>::read(machine, name).map(|attr| MachineAttribute {
+            value: attr.value,
+            validity: attr.validity,
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_attribute(owner: &T::AccountId, machine: &T::AccountId, name: &[u8], value: &[u8]) {
+        DidPallet::<T>::add_attribute(
+            frame_system::RawOrigin::Signed(owner.clone()).into(),
+            machine.clone(),
+            name.to_vec(),
+            value.to_vec(),
+            None,
+        )
+        .expect("attribute can be added");
+    }
+}
+
+/// Prefix of the storage keys of `InMemoryIdentity`.
+const IN_MEMORY_PREFIX: &[u8] = b":peaq_mor:identity:";
+
+/// Simple machine identities for tests, e.g. of runtimes or pallets, which depend on this
+/// pallet. Identities will be kept in the storage of the test externalities, so each test
+/// starts without any.
+pub struct InMemoryIdentity<AccountId, BlockNumber>(PhantomData<(AccountId, BlockNumber)>);
+
+impl<AccountId, BlockNumber> InMemoryIdentity<AccountId, BlockNumber>
+where
+    AccountId: Encode + Decode,
+    BlockNumber: Encode + Decode,
+{
+    /// Sets (or removes) the owner of a machine's identity.
+    pub fn set_owner(machine: &AccountId, owner: Option<&AccountId>) {
+        let key = (IN_MEMORY_PREFIX, machine).encode();
+        match owner {
+            Some(o) => unhashed::put(&key, o),
+            None => unhashed::kill(&key),
+        }
+    }
+
+    /// Sets (or removes) an attribute of a machine's identity.
+    pub fn set_attribute(
+        machine: &AccountId,
+        name: &[u8],
+        attribute: Option<MachineAttribute<BlockNumber>>,
+    ) {
+        let key = (IN_MEMORY_PREFIX, machine, name).encode();
+        match attribute {
+            Some(a) => unhashed::put(&key, &a),
+            None => unhashed::kill(&key),
+        }
+    }
+}
+
+impl<AccountId, BlockNumber> MachineIdentityProvider<AccountId>
+    for InMemoryIdentity<AccountId, BlockNumber>
+where
+    AccountId: Clone + Encode + Decode + PartialEq,
+    BlockNumber: Encode + Decode + sp_runtime::traits::Bounded,
+{
+    type BlockNumber = BlockNumber;
+
+    fn is_owner(owner: &AccountId, machine: &AccountId) -> bool {
+        Self::owner_of(machine).as_ref() == Some(owner)
+    }
+
+    fn owner_of(machine: &AccountId) -> Option<AccountId> {
+        unhashed::get(&(IN_MEMORY_PREFIX, machine).encode())
+    }
+
+    fn attribute_of(machine: &AccountId, name: &[u8]) -> Option<MachineAttribute<BlockNumber>> {
+        unhashed::get(&(IN_MEMORY_PREFIX, machine, name).encode())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_attribute(owner: &AccountId, machine: &AccountId, name: &[u8], value: &[u8]) {
+        Self::set_owner(machine, Some(owner));
+        Self::set_attribute(
+            machine,
+            name,
+            Some(MachineAttribute {
+                value: value.to_vec(),
+                validity: BlockNumber::max_value(),
+            }),
+        );
+    }
+}
@@ -21,7 +21,8 @@
 //!
 //! - **Machine:** By machine a true device in real world is meant, e.g. a charging station
 //!     or electrical car. For demonstration purpose this can be a Raspberry Pi. A machine
-//!     has its own account and will be identified by the Peaq-DID pallet.
+//!     has its own account and will be identified by the Peaq-DID pallet, or by another
+//!     provider of machine identities (see `Config::Identity` and module `identity`).
 //!
 //! - **Machine owner:** In abstract here we talk about a person who owns that machine and
 //!     will administrate it. In a blockchain's point of view we talk about an account.
@@ -59,6 +60,9 @@
 //!     ```ignore
 //!     parameter_types! {
 //!         pub const PotMorId: PalletId = PalletId(*b"PotMchOw");
+//!         pub const PotMorCeiling: u128 = 100_000_000_000_000_000_000_000;
+//!         pub const ReapMorBounty: u128 = 10_000_000_000_000_000;
+//!         pub const MorOffenceSlash: Perbill = Perbill::from_percent(10);
 //!         pub const ExistentialDeposit: u128 = 500;
 //!     }
 //!     ```
@@ -69,7 +73,26 @@
 //!         type Event = Event;
 //!         type ExistentialDeposit = ExistentialDeposit;
 //!         type Currency = Balances;
+//!         type RuntimeHoldReason = RuntimeHoldReason;
+//!         type Assets = Assets;
 //!         type PotId = PotMorId;
+//!         type AdminOrigin = EnsureRoot<AccountId>;
+//!         type PotCeiling = PotMorCeiling;
+//!         type SurplusDestination = Treasury;
+//!         type ReapBounty = ReapMorBounty;
+//!         type ReporterOrigin = EnsureSignedBy<MorReporters, AccountId>;
+//!         type OffenceSlash = MorOffenceSlash;
+//!         type OffenceFreezePeriods = ConstU32<6>;
+//!         type MaxOffences = ConstU32<16>;
+//!         type Identity = peaq_pallet_mor::identity::PeaqDidIdentity<Runtime>;
+//!         type AuthorityId = peaq_pallet_mor::offchain::crypto::AuthId;
+//!         type MaxAttestations = ConstU32<64>;
+//!         type LivenessThreshold = ConstU32<2>;
+//!         type MachineSignature = Signature;
+//!         type MachinePublic = <Signature as Verify>::Signer;
+//!         type HeartbeatPriority = ConstU64<{ TransactionPriority::MAX / 2 }>;
+//!         type MachineFeelessCalls = MorMachineCalls;
+//!         type MachineFeeQuota = ConstU32<10>;
 //!         type WeightInfo = peaq_pallet_mor::weights::SubstrateWeight<Runtime>;
 //!     }
 //!     ```
@@ -84,7 +107,7 @@
 //!         {
 //!             System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 //!             // ...
-//!             PeaqMor: peaq_pallet_mor::{Pallet, Call, Config<T>, Storage, Event<T>}
+//!             PeaqMor: peaq_pallet_mor::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned}
 //!         }
 //!     }
 //!     ```
@@ -103,12 +126,49 @@
 //!             machine_usage_fee_min: 1_000u128,
 //!             machine_usage_fee_max: 3_000_000_000_000_000_000u128,
 //!             track_n_block_rewards: 200,
+//!             max_mint_per_period: 100_000_000_000_000_000_000u128,
+//!             mint_period: 7_200,
+//!             emission_budget: 10_000_000_000_000_000_000_000u128,
+//!             pot_reserve: 1_000_000_000_000_000_000u128,
+//!             pot_low_threshold: 10_000_000_000_000_000_000u128,
 //!         },
 //!     },
 //!     ```
 //!
 //! - Implement a mechanism to fill that Pot-account `PotMorId`
 //!
+//! - Wrap the runtime's fee payment in `SignedExtra` by `extension::CheckMorMachineFee`,
+//!     so registered machines can call whitelisted calls without fees
+//!
+//! - Implement `CreateSignedTransaction` for the runtime and insert keys of type
+//!     `offchain::KEY_TYPE` into the keystores of the reporters' nodes, so the offchain
+//!     worker can submit liveness attestations (see module `offchain`)
+//!
+//! - Implement the runtime API within `impl_runtime_apis!` macro:
+//!     ```ignore
+//!     impl peaq_pallet_mor::runtime_api::PeaqMorApi<Block, AccountId, Balance, BlockNumber>
+//!         for Runtime
+//!     {
+//!         fn machine_metadata(machine: AccountId) -> Option<MachineMetadata> {
+//!             PeaqMor::machine_metadata(&machine)
+//!         }
+//!
+//!         fn machine_stats(
+//!             machine: AccountId,
+//!         ) -> Option<MachineStatistics<Balance, BlockNumber>> {
+//!             PeaqMor::machine_stats(&machine)
+//!         }
+//!
+//!         fn owner_stats(owner: AccountId) -> ActivityStatistics<Balance, BlockNumber> {
+//!             PeaqMor::owner_stats(&owner)
+//!         }
+//!
+//!         fn mor_metrics() -> MorMetrics<Balance> {
+//!             PeaqMor::mor_metrics()
+//!         }
+//!     }
+//!     ```
+//!
 //! ### Dispatchable Functions (Extrinsics)
 //!
 //! - `get_registration_reward` - As it says, after registering a new machine with to
@@ -120,12 +180,85 @@
 //!     pot to the account of the machine owner.
 //!
 //! - `pay_machine_usage` - Simulates the payment of a used machine. Tokens will be
-//!     minted, because currently users have no tokens on their accounts.
+//!     minted, because currently users have no tokens on their accounts. The amount
+//!     has to match the machine's tariff, if its owner has published one.
+//!
+//! - `set_machine_tariff` - The owner of a registered machine can publish a tariff
+//!     (flat fee, price per unit, currency) for using that machine. The tariff has
+//!     to be within the configured limits of machine usage fees.
+//!
+//! - `pay_machine_usage_in_asset` - Pays for the usage of a machine in a whitelisted
+//!     asset, e.g. a stablecoin. Tokens will be transfered from the user to the machine.
+//!
+//! - `set_accepted_asset` - Whitelists an asset for machine usage payments with its own
+//!     limits of usage fees. This can only be done by a sudo-user.
+//!
+//! - `set_reward_asset` - Sets the whitelisted asset in which online rewards will be paid
+//!     out of the pot. By default online rewards are paid in the native currency. This can
+//!     only be done by a sudo-user.
 //!
 //! - `set_configuration` - Setting a new pallet configuration. This can only be done
 //!     by a sudo-user. For details about configuration have a look at the definition
 //!     of `MorConfig`.
 //!
+//! - `withdraw_from_pot` - Withdraws funds above the reserve floor from the pot, e.g. to
+//!     a treasury. This can only be done by the configured admin origin.
+//!
+//! - `donate_to_pot` - Anyone can donate funds to the pot, at least the existential deposit.
+//!     Donations will be added to the latest collected block-reward.
+//!
+//! - `set_machine_metadata` - The owner of a registered machine can publish metadata of
+//!     the machine: its type, location (geohash), capacity, firmware version, the name
+//!     of an attribute in Peaq-DID, which refers to the machine's document, and an
+//!     endpoint, which will be pinged by the offchain worker.
+//!
+//! - `create_campaign` / `close_campaign` - Reward campaigns have their own pot, which will
+//!     be funded from the pallet's pot, a budget, a reward rate, a claim interval, a start
+//!     and an end block and eligibility rules based on Peaq-DID attributes. Closing a
+//!     campaign returns its remaining funds. This can only be done by the admin origin.
+//!
+//! - `claim_campaign_reward` - The owner of a registered and eligible machine can claim
+//!     the campaign's reward once per claim interval, while the campaign is running.
+//!
+//! - `set_eligibility_rules` - Configures Peaq-DID attributes (and optionally their values),
+//!     which machines need to be registered and to receive online rewards. Expired
+//!     attributes do not count. This can only be done by the admin origin.
+//!
+//! - `reap_stale_machine` - Anyone can clean up a registration, which is no longer backed
+//!     by Peaq-DID. The machine's new owner in Peaq-DID gets it re-assigned, anyone else
+//!     gets a small bounty out of the pot for removing it. The bounty will only be paid
+//!     once per machine.
+//!
+//! - `bond` / `unbond` / `withdraw_unbonded` - The owner of a registered machine can bond
+//!     tokens behind it, which increase its online rewards. Unbonded tokens stay held for
+//!     the unbonding period, so they can still be slashed.
+//!
+//! - `set_staking_config` - Configures the curve, the full stake, the maximum bonus and the
+//!     unbonding period of staking. This can only be done by the admin origin.
+//!
+//! - `report_misbehaviour` / `adjudicate_misbehaviour` - Designated reporters can report
+//!     misbehaving machines, e.g. for spoofed heartbeats, and the admin origin confirms or
+//!     dismisses the reports. Confirmed offences slash the machine's bond into the pot and
+//!     freeze its online rewards for some periods.
+//!
+//! - `submit_liveness` - Designated reporters submit the liveness of machines, which their
+//!     offchain worker has verified by pinging the endpoints in the machines' metadata at
+//!     the start of each reward period. A machine's liveness changes, once enough reporters
+//!     agree on it. Machines, which have been attested offline, will not receive online
+//!     rewards for the rest of the period, unless they send a heartbeat.
+//!
+//! - `submit_heartbeat` - Machines prove their liveness by unsigned heartbeats, which they
+//!     sign with their account's key, so they don't need funds for transaction fees. Each
+//!     registered machine can send one heartbeat per reward period, which proves its
+//!     liveness for that period. The signed payload is prefixed by the chain's genesis
+//!     hash, so it cannot be replayed on other chains.
+//!
+//! In idle time of blocks, funds above the pot's ceiling will be swept to the configured
+//! surplus destination.
+//!
+//! Other pallets of the runtime can compose this functionality via the trait `MorInterface`
+//! (see module `mor`) without going through extrinsics.
+//!
 //! - Remaining methods are temporary for development and debug purpose.
 //!
 
@@ -148,8 +281,12 @@ mod tests;
 mod benchmarking;
 
 pub mod error;
+pub mod extension;
+pub mod identity;
 pub mod migrations;
 pub mod mor;
+pub mod offchain;
+pub mod runtime_api;
 pub mod types;
 
 pub mod weightinfo;
@@ -159,31 +296,47 @@ pub use weightinfo::WeightInfo;
 #[frame_support::pallet]
 pub mod pallet {
 
-    use core::cmp::Ordering;
     use frame_support::BoundedVec;
     use frame_support::{
         pallet_prelude::*,
+        storage::with_storage_layer,
         traits::{
-            Currency, ExistenceRequirement, Get, Imbalance, LockableCurrency, ReservableCurrency,
+            fungible, fungibles,
+            tokens::{Fortitude, Precision, Preservation, Provenance, Restriction},
+            Contains, Get, Imbalance, OnUnbalanced,
         },
         PalletId,
     };
-    use frame_system::pallet_prelude::*;
+    use frame_system::{
+        offchain::{AppCrypto, CreateSignedTransaction},
+        pallet_prelude::*,
+    };
     use sp_io::hashing::blake2_256;
-    use sp_runtime::traits::{AccountIdConversion, One, Zero};
-    use sp_std::vec;
-
-    use peaq_pallet_did::{did::Did, Pallet as DidPallet};
+    use sp_runtime::{
+        traits::{
+            AccountIdConversion, IdentifyAccount, One, Saturating, UniqueSaturatedInto, Verify,
+            Zero,
+        },
+        Perbill,
+    };
+    use sp_std::{vec, vec::Vec};
 
     use super::WeightInfo;
     use crate::{
         error::{
             MorError,
             MorError::{
-                DidAuthorizationFailed, InsufficientTokensInPot, MachineAlreadyRegistered,
-                MachineNotRegistered, MachinePaymentOutOfRange, MorAuthorizationFailed,
-                MorConfigIsNotConsistent, TokensCouldNotBeTransfered, UnexpectedDidError,
-                UnknownError,
+                AssetNotAccepted, AssetWithoutRewardRate, BondIsUnbonding, CampaignBudgetExhausted,
+                CampaignIsNotConsistent, CampaignNotActive, CampaignNotFound,
+                CampaignRewardAlreadyClaimed, DidAttributeNotFound, DidAuthorizationFailed,
+                DonationTooSmall, EmissionBudgetExhausted, HeartbeatAlreadyReceived,
+                HeartbeatOutdated, InsufficientBond, InsufficientTokensInPot,
+                MachineAlreadyRegistered, MachineMetadataIsNotConsistent, MachineNotBonded,
+                MachineNotEligible, MachineNotRegistered, MachineNotStale, MachineOffline,
+                MachinePaymentDoesNotMatchTariff, MachinePaymentOutOfRange, MachineRewardsFrozen,
+                MachineTariffOutOfRange, MintPeriodCapReached, MorAuthorizationFailed,
+                MorConfigIsNotConsistent, ReportAlreadyPending, ReportNotFound,
+                TokensCouldNotBeTransfered, UnexpectedDidError, UnknownError,
             },
             MorResult,
         },
@@ -204,7 +357,7 @@ pub mod pallet {
     }
 
     const MAX_BLOCK_REWARD_NUM: u32 = u8::MAX as u32;
-    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+    pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -212,7 +365,7 @@ pub mod pallet {
 
     /// Configuration trait of this pallet.
     #[pallet::config]
-    pub trait Config: frame_system::Config + peaq_pallet_did::Config
+    pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config
     where
         BalanceOf<Self>: Zero + One + PartialOrd + Eq,
     {
@@ -223,16 +376,93 @@ pub mod pallet {
         #[pallet::constant]
         type ExistentialDeposit: Get<BalanceOf<Self>>;
 
-        /// The currency type.
-        type Currency: Currency<Self::AccountId>
-            + ReservableCurrency<Self::AccountId>
-            + LockableCurrency<Self::AccountId>
-            + Eq;
+        /// The currency type. Funds will be held for the reasons of `HoldReason`.
+        type Currency: fungible::Inspect<Self::AccountId>
+            + fungible::Mutate<Self::AccountId>
+            + fungible::Balanced<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// The overarching hold reason of the runtime, which has to include this pallet's
+        /// `HoldReason`. It will be used to identify holds and freezes of this pallet.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Fungible assets (e.g. stablecoins), which can be used for machine usage payments
+        /// besides the native currency. Only assets, which have been whitelisted by the
+        /// pallet's configuration, will be accepted.
+        type Assets: fungibles::Inspect<Self::AccountId, Balance = BalanceOf<Self>>
+            + fungibles::Mutate<Self::AccountId>;
 
         /// Account Identifier from which the internal Pot is generated.
         #[pallet::constant]
         type PotId: Get<PalletId>;
 
+        /// Origin, which is allowed to withdraw funds from the pot, e.g. to a treasury.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum balance of the pot. Funds above this ceiling will be swept to the
+        /// `SurplusDestination` in idle time of blocks.
+        #[pallet::constant]
+        type PotCeiling: Get<BalanceOf<Self>>;
+
+        /// Handler for the surplus funds, which have been swept out of the pot.
+        type SurplusDestination: OnUnbalanced<fungible::Credit<Self::AccountId, Self::Currency>>;
+
+        /// Bounty, which will be paid out of the pot for reaping a stale registration.
+        #[pallet::constant]
+        type ReapBounty: Get<BalanceOf<Self>>;
+
+        /// Origin of designated reporters of misbehaving machines, e.g. validators.
+        type ReporterOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// Share of a machine's bond, which will be slashed for a confirmed offence.
+        #[pallet::constant]
+        type OffenceSlash: Get<Perbill>;
+
+        /// Number of reward periods (see `MorConfig::track_n_block_rewards`), for which the
+        /// online rewards of a machine will be frozen after a confirmed offence.
+        #[pallet::constant]
+        type OffenceFreezePeriods: Get<u32>;
+
+        /// Maximum number of offences, which will be kept in the history of a machine.
+        #[pallet::constant]
+        type MaxOffences: Get<u32>;
+
+        /// Provider of machine identities and their attributes, e.g. Peaq-DID.
+        type Identity: MachineIdentityProvider<Self::AccountId, BlockNumber = BlockNumberFor<Self>>;
+
+        /// Keys of the offchain worker, which sign the liveness attestations. The accounts
+        /// of these keys have to be accepted by the `ReporterOrigin`.
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+        /// Maximum number of liveness attestations in one transaction, and of a machine's
+        /// attestations, which will be removed together with its registration.
+        #[pallet::constant]
+        type MaxAttestations: Get<u32>;
+
+        /// Minimum number of designated reporters, which have to attest the same liveness of
+        /// a machine in a reward period, before it changes. They also need the majority of
+        /// that period's attestations.
+        #[pallet::constant]
+        type LivenessThreshold: Get<u32>;
+
+        /// Signature, which machines sign their heartbeats with, using their account's key.
+        type MachineSignature: Verify<Signer = Self::MachinePublic> + Parameter;
+
+        /// Public key of a machine's account, which verifies the machine's signatures.
+        type MachinePublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Priority of unsigned heartbeats in the transaction pool.
+        #[pallet::constant]
+        type HeartbeatPriority: Get<TransactionPriority>;
+
+        /// Calls of this pallet, whose transaction fees will be waived for registered
+        /// machines, see module `extension`.
+        type MachineFeelessCalls: Contains<Call<Self>>;
+
+        /// Number of fee-less transactions of a registered machine per reward period.
+        #[pallet::constant]
+        type MachineFeeQuota: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -246,6 +476,15 @@ pub mod pallet {
     pub(super) type MachineRegister<T: Config> =
         StorageMap<_, Blake2_128Concat, [u8; 32], [u8; 32], ValueQuery>;
 
+    /// This storage records the accounts of machine and owner for each entry in
+    /// MachineRegister, so the registration can be verified against Peaq-DID later on.
+    /// Machines, which have been registered before this storage has been introduced, have
+    /// no entry here and are flagged as unverifiable.
+    #[pallet::storage]
+    #[pallet::getter(fn machine_accounts_of)]
+    pub(super) type MachineAccounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], (T::AccountId, T::AccountId), OptionQuery>;
+
     /// Storage for recording incoming block-rewards. Its purpose is to be able to
     /// calculate the amount (sum) of all collected block-rewards within the defined
     /// time period.
@@ -270,39 +509,434 @@ pub mod pallet {
     pub(super) type MorConfigStorage<T: Config> =
         StorageValue<_, MorConfig<BalanceOf<T>>, ValueQuery>;
 
+    /// Storage for tracking the amount of minted tokens within the current minting period.
+    /// BlockNumber stores the index of the period (block number divided by period length).
+    /// Balance stores the amount of tokens, which have been minted in that period.
+    #[pallet::storage]
+    #[pallet::getter(fn minted_in_period_of)]
+    pub(super) type MintedInPeriod<T: Config> =
+        StorageValue<_, (BlockNumberFor<T>, BalanceOf<T>), ValueQuery>;
+
+    /// This storage is for the sum over all tokens, which have been minted by this pallet.
+    /// It will be checked against the lifetime emission budget of the MorConfig.
+    #[pallet::storage]
+    #[pallet::getter(fn total_emission_of)]
+    pub(super) type TotalEmission<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// This storage flags, whether the pot's balance above the reserve floor is below the
+    /// configured threshold. It is used to announce only the crossing of the threshold.
+    #[pallet::storage]
+    #[pallet::getter(fn is_pot_low)]
+    pub(super) type PotIsLow<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// This storage holds the usage tariffs, which have been published by machine owners.
+    /// Key of the StorageMap will be the machine's account (hashed like in MachineRegister),
+    /// value the tariff. Machines without a tariff will only be limited by the MorConfig.
+    #[pallet::storage]
+    #[pallet::getter(fn machine_tariff_of)]
+    pub(super) type MachineTariffs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        [u8; 32],
+        MachineTariff<BalanceOf<T>, AssetIdOf<T>>,
+        OptionQuery,
+    >;
+
+    /// This storage holds the metadata of machines, which has been published by their
+    /// owners. Key of the StorageMap will be the machine's account (hashed like in
+    /// MachineRegister), value the metadata.
+    #[pallet::storage]
+    #[pallet::getter(fn machine_metadata_of)]
+    pub(super) type MachineMetadataOf<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], MachineMetadata, OptionQuery>;
+
+    /// This storage holds the eligibility rules, which have been configured by governance.
+    /// Machines have to fulfil all of them to be registered and to receive online rewards.
+    #[pallet::storage]
+    #[pallet::getter(fn eligibility_rules)]
+    pub(super) type MachineEligibilityRules<T: Config> =
+        StorageValue<_, EligibilityRules, ValueQuery>;
+
+    /// This storage is the whitelist of assets, which are accepted for machine usage
+    /// payments. Each asset has its own limits of usage fees, see AssetConfig.
+    #[pallet::storage]
+    #[pallet::getter(fn accepted_asset_of)]
+    pub(super) type AcceptedAssets<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, AssetConfig<BalanceOf<T>>, OptionQuery>;
+
+    /// This storage holds the configuration of staking, see `StakingConfig`.
+    #[pallet::storage]
+    #[pallet::getter(fn staking_config_of)]
+    pub(super) type StakingConfigStorage<T: Config> =
+        StorageValue<_, StakingConfig<BalanceOf<T>, BlockNumberFor<T>>, ValueQuery>;
+
+    /// This storage holds the tokens, which have been bonded behind machines. Key of the
+    /// StorageMap will be the machine's account (hashed like in MachineRegister).
+    #[pallet::storage]
+    #[pallet::getter(fn machine_bond_of)]
+    pub(super) type MachineBonds<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], MachineBondOf<T>, OptionQuery>;
+
+    /// This storage records the one-time payments, which have been made for machines. Key
+    /// of the StorageMap will be the machine's account (hashed like in MachineRegister).
+    /// Entries will not be removed together with a machine's registration.
+    #[pallet::storage]
+    #[pallet::getter(fn machine_tombstone_of)]
+    pub(super) type MachineTombstones<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], MachineTombstone, ValueQuery>;
+
+    /// This storage holds the reports of misbehaving machines, which await adjudication.
+    /// Key of the StorageMap will be the machine's account (hashed like in MachineRegister).
+    #[pallet::storage]
+    #[pallet::getter(fn pending_report_of)]
+    pub(super) type PendingReports<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        [u8; 32],
+        MisbehaviourReport<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// This storage holds the confirmed offences of machines, the oldest ones will be
+    /// dropped, when the history is full.
+    #[pallet::storage]
+    #[pallet::getter(fn offences_of)]
+    pub(super) type OffenceHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        [u8; 32],
+        BoundedVec<Offence<BalanceOf<T>, BlockNumberFor<T>>, T::MaxOffences>,
+        ValueQuery,
+    >;
+
+    /// This storage holds the block, until which the online rewards of a machine are frozen
+    /// because of an offence.
+    #[pallet::storage]
+    #[pallet::getter(fn rewards_frozen_until)]
+    pub(super) type RewardsFrozenUntil<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], BlockNumberFor<T>, OptionQuery>;
+
+    /// This storage holds the last liveness of a machine, which the offchain workers of the
+    /// designated reporters have agreed on, see `LivenessTally`.
+    #[pallet::storage]
+    #[pallet::getter(fn liveness_of)]
+    pub(super) type MachineLiveness<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], Liveness<BlockNumberFor<T>>, OptionQuery>;
+
+    /// This storage holds the last liveness attestation of each reporter for a machine, i.e.
+    /// the index of the reward period and whether the machine has been online. First key is
+    /// the machine's hash, second key the reporter.
+    #[pallet::storage]
+    #[pallet::getter(fn liveness_attestation_of)]
+    pub(super) type LivenessAttestations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        [u8; 32],
+        Blake2_128Concat,
+        T::AccountId,
+        (u32, bool),
+        OptionQuery,
+    >;
+
+    /// This storage counts the liveness attestations of a machine in the latest reward
+    /// period, in which it has been attested.
+    #[pallet::storage]
+    #[pallet::getter(fn liveness_tally_of)]
+    pub(super) type LivenessTallies<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], LivenessTally, ValueQuery>;
+
+    /// This storage holds the index of the reward period, in which a machine has sent its
+    /// last heartbeat. Machines can send one heartbeat per period, which proves their
+    /// liveness in that period.
+    #[pallet::storage]
+    #[pallet::getter(fn last_heartbeat_of)]
+    pub(super) type LastHeartbeat<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], u32, OptionQuery>;
+
+    /// This storage tracks the fee-less transactions of machines, see module `extension`.
+    /// First u32 stores the index of the reward period, second u32 the number of fee-less
+    /// transactions in that period.
+    #[pallet::storage]
+    #[pallet::getter(fn feeless_calls_used_of)]
+    pub(super) type FeelessCallsUsed<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], (u32, u32), ValueQuery>;
+
+    /// This storage holds the protocol-wide metrics, see `MorMetrics`.
+    #[pallet::storage]
+    #[pallet::getter(fn mor_metrics_of)]
+    pub(super) type MorMetricsStorage<T: Config> =
+        StorageValue<_, MorMetrics<BalanceOf<T>>, ValueQuery>;
+
+    /// This storage holds the lifetime statistics of each registered machine, see
+    /// `MachineStatistics`. Key is the machine's hash.
+    #[pallet::storage]
+    #[pallet::getter(fn machine_stats_of)]
+    pub(super) type MachineStats<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        [u8; 32],
+        MachineStatistics<BalanceOf<T>, BlockNumberFor<T>>,
+        ValueQuery,
+    >;
+
+    /// This storage aggregates the statistics of all machines of an owner, also of those,
+    /// which have been removed or re-assigned since. Key is the owner's hash.
+    #[pallet::storage]
+    #[pallet::getter(fn owner_stats_of)]
+    pub(super) type OwnerStats<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        [u8; 32],
+        ActivityStatistics<BalanceOf<T>, BlockNumberFor<T>>,
+        ValueQuery,
+    >;
+
+    /// This storage holds the asset, in which online rewards will be paid out of the pot.
+    /// If not set, online rewards will be paid in the native currency.
+    #[pallet::storage]
+    #[pallet::getter(fn reward_asset_of)]
+    pub(super) type RewardAsset<T: Config> = StorageValue<_, AssetIdOf<T>, OptionQuery>;
+
+    /// This storage holds the reward campaigns, which have been created by the admin origin.
+    /// Each campaign has its own pot, which is a sub-account of the pallet's pot.
+    #[pallet::storage]
+    #[pallet::getter(fn campaign_of)]
+    pub(super) type Campaigns<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        CampaignId,
+        Campaign<BalanceOf<T>, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// This storage holds the identifier of the next campaign to be created. Identifiers
+    /// will never be reused.
+    #[pallet::storage]
+    #[pallet::getter(fn next_campaign_id)]
+    pub(super) type NextCampaignId<T: Config> = StorageValue<_, CampaignId, ValueQuery>;
+
+    /// This storage tracks the block, in which a machine's reward of a campaign has been
+    /// claimed the last time. First key is the campaign, second key the machine's hash.
+    #[pallet::storage]
+    #[pallet::getter(fn campaign_claim_of)]
+    pub(super) type CampaignClaims<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        CampaignId,
+        Blake2_128Concat,
+        [u8; 32],
+        BlockNumberFor<T>,
+        OptionQuery,
+    >;
+
     /// Possible Event types of this pallet.
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Machine has been rewarded by minting tokens.
-        MintedTokens(BalanceOf<T>),
+        /// Tokens have been minted to an account. Also reports the remaining amount, which
+        /// can be minted in the current period and the remaining emission budget.
+        MintedTokens {
+            account: T::AccountId,
+            amount: BalanceOf<T>,
+            remaining_in_period: BalanceOf<T>,
+            remaining_budget: BalanceOf<T>,
+        },
         /// The pallet's configuration has been updated.
-        MorConfigChanged(MorConfig<BalanceOf<T>>),
-        /// Fetches the pallet's configuration.
-        FetchedMorConfig(MorConfig<BalanceOf<T>>),
+        ConfigUpdated {
+            old: MorConfig<BalanceOf<T>>,
+            new: MorConfig<BalanceOf<T>>,
+        },
         /// Temporary for development. Fetched balance of MOR pot.
-        FetchedPotBalance(BalanceOf<T>),
-        /// Temporary for development. Fetched current amount of rewarding.
-        FetchedCurrentRewarding(BalanceOf<T>),
-        /// Sent when machine usage has been payed.
-        MachineUsagePayed(T::AccountId, BalanceOf<T>),
-        /// Sent when the online rewards have been transfered.
-        OnlineRewardsPayed(T::AccountId, BalanceOf<T>),
-        /// Sent when a registration rewards have been transfered.
-        RegistrationRewardPayed(T::AccountId, BalanceOf<T>),
+        FetchedPotBalance { balance: BalanceOf<T> },
+        /// Sent when the pot's balance above the reserve floor falls below the configured
+        /// threshold. Online rewards will be scaled down from now on.
+        PotLow { available: BalanceOf<T> },
+        /// Sent when funds have been withdrawn from the pot by the admin origin.
+        PotWithdrawn {
+            destination: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when someone has donated funds to the pot.
+        PotDonated {
+            donor: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when funds above the pot's ceiling have been swept out of the pot.
+        PotSwept { amount: BalanceOf<T> },
+        /// Sent when a reward campaign has been created, reports its pot and budget.
+        CampaignCreated {
+            campaign_id: CampaignId,
+            pot: T::AccountId,
+            budget: BalanceOf<T>,
+        },
+        /// Sent when the owner of a machine has been rewarded by a campaign.
+        CampaignRewardPaid {
+            campaign_id: CampaignId,
+            owner: T::AccountId,
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when a campaign has been closed, reports the amount returned to the pot.
+        CampaignClosed {
+            campaign_id: CampaignId,
+            returned: BalanceOf<T>,
+        },
+        /// Sent when machine usage has been paid. The fee is the part of the amount, which
+        /// is the flat fee of the machine's tariff, or zero if there is no tariff.
+        UsagePaid {
+            payer: T::AccountId,
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+        },
+        /// Sent when the online rewards of a machine have been paid to its owner for the
+        /// given reward period.
+        OnlineRewardPaid {
+            owner: T::AccountId,
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+            period: u32,
+        },
+        /// Sent when a machine has been registered and its owner got the registration reward.
+        MachineRegistered {
+            owner: T::AccountId,
+            machine: T::AccountId,
+            reward: BalanceOf<T>,
+        },
+        /// Sent when the owner of a machine has set (or removed) the machine's tariff.
+        MachineTariffChanged {
+            machine: T::AccountId,
+            tariff: Option<MachineTariff<BalanceOf<T>, AssetIdOf<T>>>,
+        },
+        /// Sent when the owner of a machine has set (or removed) the machine's metadata.
+        MachineMetadataChanged {
+            machine: T::AccountId,
+            metadata: Option<MachineMetadata>,
+        },
+        /// Sent when machine usage has been paid in a whitelisted asset. The fee is the same
+        /// as in `UsagePaid`.
+        UsagePaidInAsset {
+            payer: T::AccountId,
+            machine: T::AccountId,
+            asset: AssetIdOf<T>,
+            amount: BalanceOf<T>,
+            fee: BalanceOf<T>,
+        },
+        /// Sent when an asset has been whitelisted (or removed from the whitelist).
+        AcceptedAssetChanged {
+            asset: AssetIdOf<T>,
+            config: Option<AssetConfig<BalanceOf<T>>>,
+        },
+        /// Sent when the currency of online rewards has been changed.
+        RewardAssetChanged { asset: Option<AssetIdOf<T>> },
+        /// Sent when the eligibility rules for machines have been changed.
+        EligibilityRulesChanged { rules: EligibilityRules },
+        /// Sent when a stale registration of a machine has been re-assigned to its new
+        /// owner in Peaq-DID.
+        MachineReassigned {
+            machine: T::AccountId,
+            owner: T::AccountId,
+        },
+        /// Sent when a stale registration of a machine has been reaped.
+        StaleMachineReaped {
+            machine: T::AccountId,
+            reaper: T::AccountId,
+            bounty: BalanceOf<T>,
+        },
+        /// Sent when a registered machine has been rewarded by another pallet.
+        MachineRewarded {
+            source: T::AccountId,
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when tokens have been bonded behind a machine.
+        Bonded {
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when tokens have been unbonded. They can be withdrawn from `unlock_at` on.
+        Unbonded {
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+            unlock_at: BlockNumberFor<T>,
+        },
+        /// Sent when unbonded tokens have been withdrawn.
+        BondWithdrawn {
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when tokens bonded behind a machine have been slashed into the pot.
+        BondSlashed {
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Sent when the staking configuration has been changed.
+        StakingConfigChanged {
+            config: StakingConfig<BalanceOf<T>, BlockNumberFor<T>>,
+        },
+        /// Sent when a machine's misbehaviour has been reported.
+        MisbehaviourReported {
+            machine: T::AccountId,
+            reporter: T::AccountId,
+        },
+        /// Sent when a machine's misbehaviour has been confirmed. Its online rewards are
+        /// frozen until the given block.
+        MisbehaviourConfirmed {
+            machine: T::AccountId,
+            slashed: BalanceOf<T>,
+            frozen_until: BlockNumberFor<T>,
+        },
+        /// Sent when a report of a machine's misbehaviour has been dismissed.
+        MisbehaviourDismissed { machine: T::AccountId },
+        /// Sent when liveness attestations have been submitted. Provides the number of
+        /// attested machines.
+        LivenessAttested { reporter: T::AccountId, count: u32 },
+        /// Sent when the reporters have agreed on a machine's liveness in a reward period.
+        LivenessAgreed {
+            machine: T::AccountId,
+            online: bool,
+            period: u32,
+        },
+        /// Sent when a machine has sent a heartbeat for the given reward period.
+        HeartbeatReceived { machine: T::AccountId, period: u32 },
     }
 
     /// For description of error types, please have a look into module error for
     /// further informations about error types.
     #[pallet::error]
     pub enum Error<T> {
+        AssetNotAccepted,
+        AssetWithoutRewardRate,
+        BondIsUnbonding,
+        CampaignBudgetExhausted,
+        CampaignIsNotConsistent,
+        CampaignNotActive,
+        CampaignNotFound,
+        CampaignRewardAlreadyClaimed,
+        DidAttributeNotFound,
         DidAuthorizationFailed,
+        DonationTooSmall,
+        EmissionBudgetExhausted,
+        HeartbeatAlreadyReceived,
+        HeartbeatOutdated,
+        InsufficientBond,
         InsufficientTokensInPot,
         MachineAlreadyRegistered,
+        MachineMetadataIsNotConsistent,
+        MachineNotBonded,
+        MachineNotEligible,
         MachineNotRegistered,
+        MachineNotStale,
+        MachineOffline,
+        MachinePaymentDoesNotMatchTariff,
         MachinePaymentOutOfRange,
+        MachineRewardsFrozen,
+        MachineTariffOutOfRange,
+        MintPeriodCapReached,
         MorAuthorizationFailed,
         MorConfigIsNotConsistent,
+        ReportAlreadyPending,
+        ReportNotFound,
         TokensCouldNotBeTransfered,
         UnexpectedDidError,
         UnknownError,
@@ -311,13 +945,40 @@ pub mod pallet {
     impl<T: Config> Error<T> {
         fn from_mor(err: MorError) -> DispatchError {
             match err {
+                AssetNotAccepted => Error::<T>::AssetNotAccepted.into(),
+                AssetWithoutRewardRate => Error::<T>::AssetWithoutRewardRate.into(),
+                BondIsUnbonding => Error::<T>::BondIsUnbonding.into(),
+                CampaignBudgetExhausted => Error::<T>::CampaignBudgetExhausted.into(),
+                CampaignIsNotConsistent => Error::<T>::CampaignIsNotConsistent.into(),
+                CampaignNotActive => Error::<T>::CampaignNotActive.into(),
+                CampaignNotFound => Error::<T>::CampaignNotFound.into(),
+                CampaignRewardAlreadyClaimed => Error::<T>::CampaignRewardAlreadyClaimed.into(),
+                DidAttributeNotFound => Error::<T>::DidAttributeNotFound.into(),
                 DidAuthorizationFailed => Error::<T>::DidAuthorizationFailed.into(),
+                DonationTooSmall => Error::<T>::DonationTooSmall.into(),
+                EmissionBudgetExhausted => Error::<T>::EmissionBudgetExhausted.into(),
+                HeartbeatAlreadyReceived => Error::<T>::HeartbeatAlreadyReceived.into(),
+                HeartbeatOutdated => Error::<T>::HeartbeatOutdated.into(),
+                InsufficientBond => Error::<T>::InsufficientBond.into(),
                 InsufficientTokensInPot => Error::<T>::InsufficientTokensInPot.into(),
                 MachineAlreadyRegistered => Error::<T>::MachineAlreadyRegistered.into(),
+                MachineMetadataIsNotConsistent => Error::<T>::MachineMetadataIsNotConsistent.into(),
+                MachineNotBonded => Error::<T>::MachineNotBonded.into(),
+                MachineNotEligible => Error::<T>::MachineNotEligible.into(),
                 MachineNotRegistered => Error::<T>::MachineNotRegistered.into(),
+                MachineNotStale => Error::<T>::MachineNotStale.into(),
+                MachineOffline => Error::<T>::MachineOffline.into(),
+                MachinePaymentDoesNotMatchTariff => {
+                    Error::<T>::MachinePaymentDoesNotMatchTariff.into()
+                }
                 MachinePaymentOutOfRange => Error::<T>::MachinePaymentOutOfRange.into(),
+                MachineRewardsFrozen => Error::<T>::MachineRewardsFrozen.into(),
+                MachineTariffOutOfRange => Error::<T>::MachineTariffOutOfRange.into(),
+                MintPeriodCapReached => Error::<T>::MintPeriodCapReached.into(),
                 MorAuthorizationFailed => Error::<T>::MorAuthorizationFailed.into(),
                 MorConfigIsNotConsistent => Error::<T>::MorConfigIsNotConsistent.into(),
+                ReportAlreadyPending => Error::<T>::ReportAlreadyPending.into(),
+                ReportNotFound => Error::<T>::ReportNotFound.into(),
                 TokensCouldNotBeTransfered => Error::<T>::TokensCouldNotBeTransfered.into(),
                 UnexpectedDidError => Error::<T>::UnexpectedDidError.into(),
                 UnknownError => Error::<T>::UnknownError.into(),
@@ -352,6 +1013,25 @@ pub mod pallet {
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
             crate::migrations::on_runtime_upgrade::<T>()
         }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let weight = WeightOf::<T>::sweep_pot();
+            if remaining_weight.all_gte(weight) {
+                Self::sweep_pot();
+                weight
+            } else {
+                Weight::zero()
+            }
+        }
+
+        fn offchain_worker(n: BlockNumberFor<T>) {
+            Self::attest_liveness(n);
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+            Self::do_try_state()
+        }
     }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -370,34 +1050,63 @@ pub mod pallet {
         ) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
-            let reward = Self::register_machine(&sender, &machine).map_err(Error::<T>::from_mor)?;
-
-            dpatch_dposit_par!(
-                Self::mint_to_account(&sender, reward),
-                Event::<T>::RegistrationRewardPayed(sender, reward)
-            )
+            Self::register_on_behalf(&sender, &machine)?;
+            Ok(())
         }
 
         /// In this early version one can collect rewards for a machine, which has been online
         /// on the network for a defined time period, see MorConfig. This method will raise
         /// errors if the authorization in Peaq-DID fails or if the machine is not registered
-        /// in Peaq-MOR.
+        /// in Peaq-MOR. If the pot runs low, the reward will be scaled down.
         #[pallet::call_index(1)]
-        #[pallet::weight(WeightOf::<T>::get_online_rewards())]
+        #[pallet::weight(
+            WeightOf::<T>::get_online_rewards().max(WeightOf::<T>::get_online_rewards_in_asset())
+        )]
         pub fn get_online_rewards(origin: OriginFor<T>, machine: T::AccountId) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
             let reward = Self::reward_machine(&sender, &machine).map_err(Error::<T>::from_mor)?;
 
-            dpatch_dposit_par!(
-                Self::transfer_from_pot(&sender, reward),
-                Event::<T>::OnlineRewardsPayed(sender, reward)
-            )
+            // Asset rewards will be converted by the asset's reward rate, both will be scaled
+            // down alike, if the pot runs low. Statistics record the native equivalent.
+            let (reward, native) = match RewardAsset::<T>::get() {
+                Some(asset) => {
+                    let config = AcceptedAssets::<T>::get(&asset)
+                        .ok_or(Error::<T>::from_mor(AssetNotAccepted))?;
+                    let amount = config.convert(reward);
+                    let scaled = Self::scale_asset_to_pot(asset.clone(), &config, amount);
+                    if scaled.is_zero() && !amount.is_zero() {
+                        return Err(Error::<T>::from_mor(InsufficientTokensInPot));
+                    }
+                    Self::transfer_asset_from_pot(asset, &sender, scaled)?;
+                    let native = Perbill::from_rational(scaled, amount) * reward;
+                    (scaled, native)
+                }
+                None => {
+                    let scaled = Self::scale_to_pot(reward);
+                    if scaled.is_zero() && !reward.is_zero() {
+                        return Err(Error::<T>::from_mor(InsufficientTokensInPot));
+                    }
+                    Self::transfer_from_pot(&sender, scaled)?;
+                    (scaled, scaled)
+                }
+            };
+            Self::note_paid_reward(native);
+            Self::note_activity(&machine, |stats, now| stats.note_reward(native, true, now));
+
+            Self::deposit_event(Event::<T>::OnlineRewardPaid {
+                owner: sender,
+                machine,
+                amount: reward,
+                period: Self::current_period(),
+            });
+            Ok(())
         }
 
         /// When using a machine, this extrinsic is about to pay the fee for the machine usage.
         /// Assumption is, that the origin is the user, which used the machine and he will pay
-        /// the fee for machine usage.
+        /// the fee for machine usage. If the machine's owner has published a tariff, the
+        /// amount has to match that tariff too.
         #[pallet::call_index(2)]
         #[pallet::weight(WeightOf::<T>::pay_machine_usage())]
         pub fn pay_machine_usage(
@@ -405,24 +1114,26 @@ pub mod pallet {
             machine: T::AccountId,
             amount: BalanceOf<T>,
         ) -> DispatchResult {
-            ensure_signed(origin)?;
+            let sender = ensure_signed(origin)?;
 
-            let config = MorConfigStorage::<T>::get();
+            let fee = Self::check_usage_payment(&machine, &TariffCurrency::Native, amount)
+                .map_err(Error::<T>::from_mor)?;
 
-            // MachineUsagePayed
-            if config.machine_usage_fee_min > amount || amount > config.machine_usage_fee_max {
-                Err(Error::<T>::from_mor(MachinePaymentOutOfRange))
-            } else {
-                dpatch_dposit_par!(
-                    Self::mint_to_account(&machine, amount),
-                    Event::<T>::MachineUsagePayed(machine, amount)
-                )
-            }
+            Self::mint_to_account(&machine, amount, MintReason::Usage)?;
+            Self::note_activity(&machine, |stats, now| stats.note_usage_payment(amount, now));
+
+            Self::deposit_event(Event::<T>::UsagePaid {
+                payer: sender,
+                machine,
+                amount,
+                fee,
+            });
+            Ok(())
         }
 
         /// Updates the pallet's configuration parameters by passing a MorConfig-struct.
         #[pallet::call_index(3)]
-        #[pallet::weight(WeightOf::<T>::set_configuration())]
+        #[pallet::weight(WeightOf::<T>::set_configuration(config.track_n_block_rewards.into()))]
         pub fn set_configuration(
             origin: OriginFor<T>,
             config: MorConfig<BalanceOf<T>>,
@@ -432,9 +1143,10 @@ pub mod pallet {
             if config.is_consistent(T::ExistentialDeposit::get()) {
                 Self::resize_track_storage(config.track_n_block_rewards)
                     .map_err(Error::<T>::from_mor)?;
+                let old = MorConfigStorage::<T>::get();
                 MorConfigStorage::<T>::put(config.clone());
 
-                Self::deposit_event(Event::<T>::MorConfigChanged(config));
+                Self::deposit_event(Event::<T>::ConfigUpdated { old, new: config });
                 Ok(())
             } else {
                 Err(Error::<T>::from_mor(MorConfigIsNotConsistent))
@@ -448,122 +1160,1523 @@ pub mod pallet {
             ensure_root(origin)?;
 
             let pot: T::AccountId = T::PotId::get().into_account_truncating();
-            let amount = T::Currency::free_balance(&pot);
+            let amount = T::Currency::balance(&pot);
 
-            Self::deposit_event(Event::<T>::FetchedPotBalance(amount));
+            Self::deposit_event(Event::<T>::FetchedPotBalance { balance: amount });
             Ok(())
         }
-    }
-
-    impl<T: Config> Pallet<T> {
-        /// This method internally initialises the pallet's storages in dependency of the given MorConfig.
-        pub(crate) fn init_storages(mor_config: &MorConfig<BalanceOf<T>>) {
-            let record_list: BoundedVec<BalanceOf<T>, ConstU32<MAX_BLOCK_REWARD_NUM>> =
-                vec![BalanceOf::<T>::zero(); mor_config.track_n_block_rewards as usize]
-                    .try_into()
-                    .expect("bound checked in match arm condition; qed");
-            let reward_record = (0u8, record_list);
 
-            MorConfigStorage::<T>::put(mor_config.clone());
-            RewardsRecordStorage::<T>::put(reward_record);
-            PeriodRewardStorage::<T>::put(BalanceOf::<T>::zero());
-        }
-    }
+        /// Publishes the usage tariff of a machine, or removes it by passing `None`. Only the
+        /// owner of a registered machine can do this, and the tariff has to be within the
+        /// usage fee limits of the pallet's configuration.
+        #[pallet::call_index(5)]
+        #[pallet::weight(WeightOf::<T>::set_machine_tariff())]
+        pub fn set_machine_tariff(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            tariff: Option<MachineTariff<BalanceOf<T>, AssetIdOf<T>>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
 
-    // See MorBalance trait definition for further details
-    impl<T: Config> MorBalance<T::AccountId, BalanceOf<T>> for Pallet<T> {
-        fn mint_to_account(account: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
-            let imbalance = T::Currency::issue(amount);
+            let machine_hash =
+                Self::check_machine_owner(&sender, &machine).map_err(Error::<T>::from_mor)?;
 
-            let amount = imbalance.peek();
+            match &tariff {
+                Some(t) => {
+                    let (fee_min, fee_max) =
+                        Self::usage_fee_range(&t.currency).map_err(Error::<T>::from_mor)?;
+                    if !t.is_within(fee_min, fee_max) {
+                        return Err(Error::<T>::from_mor(MachineTariffOutOfRange));
+                    }
+                    MachineTariffs::<T>::insert(machine_hash, t);
+                }
+                None => MachineTariffs::<T>::remove(machine_hash),
+            }
 
-            let imbalance = T::Currency::deposit_creating(account, amount);
-            Self::deposit_event(Event::<T>::MintedTokens(imbalance.peek()));
+            Self::deposit_event(Event::<T>::MachineTariffChanged { machine, tariff });
             Ok(())
         }
 
-        fn transfer_from_pot(account: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
-            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+        /// Whitelists an asset for machine usage payments with its own limits of usage fees,
+        /// or removes it from the whitelist by passing `None`. If the removed asset was used
+        /// for online rewards, or its reward rate has been reset to zero, they will be paid in
+        /// the native currency again.
+        #[pallet::call_index(6)]
+        #[pallet::weight(WeightOf::<T>::set_accepted_asset())]
+        pub fn set_accepted_asset(
+            origin: OriginFor<T>,
+            asset: AssetIdOf<T>,
+            config: Option<AssetConfig<BalanceOf<T>>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
 
-            if T::Currency::free_balance(&pot) >= amount {
-                T::Currency::transfer(&pot, account, amount, ExistenceRequirement::KeepAlive)?;
-                Ok(())
-            } else {
-                Err(Error::<T>::from_mor(InsufficientTokensInPot))
+            match &config {
+                Some(c) => {
+                    if !c.is_consistent(T::Assets::minimum_balance(asset.clone())) {
+                        return Err(Error::<T>::from_mor(MorConfigIsNotConsistent));
+                    }
+                    AcceptedAssets::<T>::insert(asset.clone(), c);
+                }
+                None => AcceptedAssets::<T>::remove(asset.clone()),
             }
+            let rewardable = config.as_ref().map_or(false, |c| !c.reward_rate.is_zero());
+            if !rewardable && RewardAsset::<T>::get() == Some(asset.clone()) {
+                RewardAsset::<T>::kill();
+                Self::deposit_event(Event::<T>::RewardAssetChanged { asset: None });
+            }
+
+            Self::deposit_event(Event::<T>::AcceptedAssetChanged { asset, config });
+            Ok(())
         }
 
-        fn log_block_rewards(amount: BalanceOf<T>) {
-            let mor_config = MorConfigStorage::<T>::get();
-            let n_blocks = mor_config.track_n_block_rewards;
+        /// Pays the fee for a machine usage in a whitelisted asset. In contrast to
+        /// `pay_machine_usage` nothing will be minted, the amount will be transfered from
+        /// the user's account to the machine's account.
+        #[pallet::call_index(7)]
+        #[pallet::weight(WeightOf::<T>::pay_machine_usage_in_asset())]
+        pub fn pay_machine_usage_in_asset(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            asset: AssetIdOf<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
 
-            // RewardsRecordStorage: (u8, Vec<BalanceOf<T>>)
-            // (Next array-slot to write in, vector of imbalances)
-            let (mut slot_cnt, mut balances) = RewardsRecordStorage::<T>::get();
-            balances[slot_cnt as usize] = amount;
-            slot_cnt += 1;
-            if slot_cnt >= n_blocks {
-                slot_cnt = 0;
-            }
+            let fee =
+                Self::check_usage_payment(&machine, &TariffCurrency::Asset(asset.clone()), amount)
+                    .map_err(Error::<T>::from_mor)?;
 
-            // PeriodRewardStorage: BalanceOf<T>
-            // Sum of last n_blocks block-rewards
-            let mut period_reward = BalanceOf::<T>::zero();
-            balances.iter().for_each(|&b| period_reward += b);
+            Self::transfer_asset(asset.clone(), &sender, &machine, amount)?;
+            // Usage revenue is recorded by its native equivalent, like asset rewards are
+            let native = AcceptedAssets::<T>::get(&asset)
+                .map_or(BalanceOf::<T>::zero(), |config| config.to_native(amount));
+            Self::note_activity(&machine, |stats, now| stats.note_usage_payment(native, now));
 
-            RewardsRecordStorage::<T>::set((slot_cnt, balances));
-            PeriodRewardStorage::<T>::set(period_reward);
+            Self::deposit_event(Event::<T>::UsagePaidInAsset {
+                payer: sender,
+                machine,
+                asset,
+                amount,
+                fee,
+            });
+            Ok(())
         }
 
-        // Use more defensive approach here, because this method is called by the runtime
-        // But the Vec to boundedVec conversion shouldn't fail, because the Vec is created by boundedVec
-        fn resize_track_storage(new_size: u8) -> MorResult<()> {
-            let new_size = new_size as usize;
-            let (_slot_cnt, balances) = RewardsRecordStorage::<T>::get();
-            let mut balances = balances.to_vec();
+        /// Sets the whitelisted asset, in which online rewards will be paid out of the pot,
+        /// or resets it to the native currency by passing `None`. The asset needs a reward
+        /// rate, which converts the rewards, see `AssetConfig`.
+        #[pallet::call_index(8)]
+        #[pallet::weight(WeightOf::<T>::set_reward_asset())]
+        pub fn set_reward_asset(
+            origin: OriginFor<T>,
+            asset: Option<AssetIdOf<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
 
-            let cur_size = balances.len();
-            match cur_size.cmp(&new_size) {
-                Ordering::Less => {
-                    balances.resize(new_size, BalanceOf::<T>::zero());
-                    let balances: BoundedVec<BalanceOf<T>, ConstU32<MAX_BLOCK_REWARD_NUM>> =
-                        balances.try_into().map_err(|_| {
-                            log::error!("conversion error");
-                            MorError::UnknownError
-                        })?;
-                    let slot_cnt = cur_size as u8;
-                    if balances.len() != new_size {
-                        log::error!(
-                            "resize error: len: {}, new_size: {}",
-                            balances.len(),
-                            new_size
-                        );
-                        return Err(MorError::UnknownError);
+            match &asset {
+                Some(a) => {
+                    let config = AcceptedAssets::<T>::get(a)
+                        .ok_or(Error::<T>::from_mor(AssetNotAccepted))?;
+                    if config.reward_rate.is_zero() {
+                        return Err(Error::<T>::from_mor(AssetWithoutRewardRate));
+                    }
+                    RewardAsset::<T>::put(a);
+                }
+                None => RewardAsset::<T>::kill(),
+            }
+
+            Self::deposit_event(Event::<T>::RewardAssetChanged { asset });
+            Ok(())
+        }
+
+        /// Withdraws funds from the pot to the given account, e.g. to a treasury. Only the
+        /// funds above the pot's reserve floor can be withdrawn.
+        #[pallet::call_index(9)]
+        #[pallet::weight(WeightOf::<T>::withdraw_from_pot())]
+        pub fn withdraw_from_pot(
+            origin: OriginFor<T>,
+            beneficiary: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            dpatch_dposit_par!(
+                Self::transfer_from_pot(&beneficiary, amount),
+                Event::<T>::PotWithdrawn {
+                    destination: beneficiary,
+                    amount
+                }
+            )
+        }
+
+        /// Donates funds to the pot. The donation will be added to the latest collected
+        /// block-reward, so it increases the online rewards of the current period without
+        /// pushing any block-reward out of the tracked ones. Donations have to be at least
+        /// the existential deposit.
+        #[pallet::call_index(10)]
+        #[pallet::weight(WeightOf::<T>::donate_to_pot())]
+        pub fn donate_to_pot(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            if amount < T::ExistentialDeposit::get() {
+                return Err(Error::<T>::from_mor(DonationTooSmall));
+            }
+
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+            T::Currency::transfer(&sender, &pot, amount, Preservation::Preserve)?;
+            Self::add_to_latest_block_reward(amount);
+
+            Self::deposit_event(Event::<T>::PotDonated {
+                donor: sender,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Creates a new reward campaign. The campaign's budget will be transfered from the
+        /// pallet's pot to the campaign's own pot. Rewards can be claimed from the start block
+        /// on, until the end block of the campaign.
+        #[allow(clippy::too_many_arguments)]
+        #[pallet::call_index(11)]
+        #[pallet::weight(WeightOf::<T>::create_campaign())]
+        pub fn create_campaign(
+            origin: OriginFor<T>,
+            name: BoundedVec<u8, ConstU32<MAX_NAME_LEN>>,
+            budget: BalanceOf<T>,
+            reward_rate: BalanceOf<T>,
+            claim_interval: BlockNumberFor<T>,
+            start: BlockNumberFor<T>,
+            end: BlockNumberFor<T>,
+            eligibility: CampaignEligibility,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let campaign = Campaign {
+                name,
+                budget,
+                paid: BalanceOf::<T>::zero(),
+                reward_rate,
+                claim_interval,
+                start,
+                end,
+                eligibility,
+            };
+            if !campaign.is_consistent() {
+                return Err(Error::<T>::from_mor(CampaignIsNotConsistent));
+            }
+
+            let campaign_id = NextCampaignId::<T>::get();
+            let account = Self::campaign_account(campaign_id);
+            Self::transfer_from_pot(&account, budget)?;
+            Campaigns::<T>::insert(campaign_id, campaign);
+            NextCampaignId::<T>::put(campaign_id.saturating_add(1));
+
+            Self::deposit_event(Event::<T>::CampaignCreated {
+                campaign_id,
+                pot: account,
+                budget,
+            });
+            Ok(())
+        }
+
+        /// Claims the reward of a campaign for a machine, which fulfils the campaign's
+        /// eligibility rules. Like for online rewards, the origin has to be the owner of the
+        /// machine, and the machine has to be registered in Peaq-MOR. Machines, which cannot
+        /// earn online rewards right now (frozen, offline or not eligible), cannot claim either.
+        #[pallet::call_index(12)]
+        #[pallet::weight(WeightOf::<T>::claim_campaign_reward())]
+        pub fn claim_campaign_reward(
+            origin: OriginFor<T>,
+            campaign_id: CampaignId,
+            machine: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let reward = Self::claim_campaign(&sender, campaign_id, &machine)
+                .map_err(Error::<T>::from_mor)?;
+
+            let account = Self::campaign_account(campaign_id);
+            T::Currency::transfer(&account, &sender, reward, Preservation::Expendable)?;
+            Self::note_paid_reward(reward);
+            Self::note_activity(&machine, |stats, now| stats.note_reward(reward, true, now));
+
+            Self::deposit_event(Event::<T>::CampaignRewardPaid {
+                campaign_id,
+                owner: sender,
+                machine,
+                amount: reward,
+            });
+            Ok(())
+        }
+
+        /// Closes a campaign and returns the remaining funds of its pot to the pallet's pot.
+        #[pallet::call_index(13)]
+        #[pallet::weight(WeightOf::<T>::close_campaign())]
+        pub fn close_campaign(origin: OriginFor<T>, campaign_id: CampaignId) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            if Campaigns::<T>::take(campaign_id).is_none() {
+                return Err(Error::<T>::from_mor(CampaignNotFound));
+            }
+            // Claim records will not be removed, because campaign identifiers will never be
+            // reused, so they cannot affect other campaigns.
+
+            let account = Self::campaign_account(campaign_id);
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+            let remaining = T::Currency::balance(&account);
+            if !remaining.is_zero() {
+                T::Currency::transfer(&account, &pot, remaining, Preservation::Expendable)?;
+            }
+
+            Self::deposit_event(Event::<T>::CampaignClosed {
+                campaign_id,
+                returned: remaining,
+            });
+            Ok(())
+        }
+
+        /// Publishes the metadata of a machine, or removes it by passing `None`. Only the
+        /// owner of a registered machine can do this, and the referenced attribute has to
+        /// exist in the machine's document in Peaq-DID.
+        #[pallet::call_index(14)]
+        #[pallet::weight(WeightOf::<T>::set_machine_metadata())]
+        pub fn set_machine_metadata(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            metadata: Option<MachineMetadata>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let machine_hash =
+                Self::check_machine_owner(&sender, &machine).map_err(Error::<T>::from_mor)?;
+
+            match &metadata {
+                Some(m) => {
+                    if !m.is_consistent() {
+                        return Err(Error::<T>::from_mor(MachineMetadataIsNotConsistent));
                     }
-                    RewardsRecordStorage::<T>::put((slot_cnt, balances));
+                    if T::Identity::attribute_of(&machine, &m.did_attribute).is_none() {
+                        return Err(Error::<T>::from_mor(DidAttributeNotFound));
+                    }
+                    MachineMetadataOf::<T>::insert(machine_hash, m);
                 }
-                Ordering::Greater => {
-                    let slot_cnt = 0u8;
-                    let balances = balances.split_off(cur_size - new_size);
-                    let balances: BoundedVec<BalanceOf<T>, ConstU32<MAX_BLOCK_REWARD_NUM>> =
-                        balances.try_into().map_err(|_| {
-                            log::error!("conversion error");
-                            MorError::UnknownError
-                        })?;
-                    if balances.len() != new_size {
-                        log::error!(
-                            "resize error: len: {}, new_size: {}",
-                            balances.len(),
-                            new_size
+                None => MachineMetadataOf::<T>::remove(machine_hash),
+            }
+
+            Self::deposit_event(Event::<T>::MachineMetadataChanged { machine, metadata });
+            Ok(())
+        }
+
+        /// Replaces the eligibility rules for machines. Machines have to fulfil all rules
+        /// to be registered and to receive online rewards. Pass an empty set to remove them.
+        #[pallet::call_index(15)]
+        #[pallet::weight(WeightOf::<T>::set_eligibility_rules())]
+        pub fn set_eligibility_rules(
+            origin: OriginFor<T>,
+            rules: EligibilityRules,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            if !rules.iter().all(|r| r.is_consistent()) {
+                return Err(Error::<T>::from_mor(MorConfigIsNotConsistent));
+            }
+
+            MachineEligibilityRules::<T>::put(&rules);
+
+            Self::deposit_event(Event::<T>::EligibilityRulesChanged { rules });
+            Ok(())
+        }
+
+        /// Cleans up the registration of a machine, which is no longer backed by Peaq-DID,
+        /// e.g. because its document has been moved to another owner. Anyone can do this:
+        /// if the sender is the machine's new owner in Peaq-DID, the registration will be
+        /// re-assigned to the sender, without the former owner's tariff and metadata.
+        /// Otherwise it will be removed and the sender gets a bounty out of the pot, unless
+        /// the sender has been the machine's owner or a bounty has already been paid for the
+        /// machine. In both cases the tokens, which the former owner has bonded behind the
+        /// machine, will be released.
+        #[pallet::call_index(16)]
+        #[pallet::weight(WeightOf::<T>::reap_stale_machine(T::MaxAttestations::get()))]
+        pub fn reap_stale_machine(origin: OriginFor<T>, machine: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let machine_hash = (machine).using_encoded(blake2_256);
+            if !MachineRegister::<T>::contains_key(machine_hash) {
+                return Err(Error::<T>::from_mor(MachineNotRegistered));
+            }
+
+            // Has the document been moved to the sender in Peaq-DID?
+            let sender_hash = (sender).using_encoded(blake2_256);
+            if MachineRegister::<T>::get(machine_hash) != sender_hash
+                && T::Identity::is_owner(&sender, &machine)
+            {
+                MachineRegister::<T>::insert(machine_hash, sender_hash);
+                MachineAccounts::<T>::insert(machine_hash, (machine.clone(), sender.clone()));
+                // Tariff and metadata, e.g. the endpoint for liveness checks, have been
+                // published by the former owner, so the new owner has to publish them again
+                MachineTariffs::<T>::remove(machine_hash);
+                MachineMetadataOf::<T>::remove(machine_hash);
+                Self::release_bond(machine_hash);
+                Self::deposit_event(Event::<T>::MachineReassigned {
+                    machine,
+                    owner: sender,
+                });
+                return Ok(());
+            }
+
+            // Registrations without accounts (from storage version 3 and before, when
+            // MachineAccounts did not exist yet) can only be re-assigned, because their owner
+            // is unknown. MachineRegister only keeps hashes, so they cannot be back-filled.
+            let owner = MachineAccounts::<T>::get(machine_hash).map(|(_, owner)| owner);
+            match &owner {
+                Some(o) if !T::Identity::is_owner(o, &machine) => {}
+                _ => return Err(Error::<T>::from_mor(MachineNotStale)),
+            }
+            Self::remove_machine(machine_hash);
+
+            // The bounty will only be paid once per machine, so it cannot be farmed by
+            // registering the same machine again and letting it go stale
+            let tombstone = MachineTombstones::<T>::get(machine_hash);
+            let bounty = if owner.as_ref() == Some(&sender) || tombstone.bounty_paid {
+                BalanceOf::<T>::zero()
+            } else {
+                T::ReapBounty::get().min(Self::pot_available(&MorConfigStorage::<T>::get()))
+            };
+            if !bounty.is_zero() {
+                Self::transfer_from_pot(&sender, bounty)?;
+                MachineTombstones::<T>::insert(
+                    machine_hash,
+                    MachineTombstone {
+                        bounty_paid: true,
+                        ..tombstone
+                    },
+                );
+            }
+
+            Self::deposit_event(Event::<T>::StaleMachineReaped {
+                machine,
+                reaper: sender,
+                bounty,
+            });
+            Ok(())
+        }
+
+        /// Bonds tokens of the sender behind a registered machine, which the sender owns.
+        /// The tokens will be held on the sender's account and increase the machine's online
+        /// rewards, see `StakingConfig`.
+        #[pallet::call_index(17)]
+        #[pallet::weight(WeightOf::<T>::bond())]
+        pub fn bond(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let machine_hash =
+                Self::check_machine_owner(&sender, &machine).map_err(Error::<T>::from_mor)?;
+            let mut bond = match MachineBonds::<T>::get(machine_hash) {
+                Some(b) if b.owner != sender => {
+                    return Err(Error::<T>::from_mor(MachineNotBonded));
+                }
+                Some(b) => b,
+                None => MachineBond {
+                    owner: sender.clone(),
+                    active: BalanceOf::<T>::zero(),
+                    unbonding: BalanceOf::<T>::zero(),
+                    unlock_at: BlockNumberFor::<T>::zero(),
+                },
+            };
+
+            T::Currency::hold(&HoldReason::MachineBond.into(), &sender, amount)?;
+            bond.active = bond.active.saturating_add(amount);
+            MachineBonds::<T>::insert(machine_hash, bond);
+
+            Self::deposit_event(Event::<T>::Bonded { machine, amount });
+            Ok(())
+        }
+
+        /// Unbonds tokens from a machine. They do not increase the machine's online rewards
+        /// anymore, but stay held until the end of the unbonding period. Unbonding again
+        /// restarts the unbonding period for all unbonded tokens.
+        #[pallet::call_index(18)]
+        #[pallet::weight(WeightOf::<T>::unbond())]
+        pub fn unbond(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let (machine_hash, mut bond) = Self::bond_of(&sender, &machine)?;
+            if bond.active < amount {
+                return Err(Error::<T>::from_mor(InsufficientBond));
+            }
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            bond.active -= amount;
+            bond.unbonding = bond.unbonding.saturating_add(amount);
+            bond.unlock_at = now.saturating_add(StakingConfigStorage::<T>::get().unbonding_period);
+            let unlock_at = bond.unlock_at;
+            MachineBonds::<T>::insert(machine_hash, bond);
+
+            Self::deposit_event(Event::<T>::Unbonded {
+                machine,
+                amount,
+                unlock_at,
+            });
+            Ok(())
+        }
+
+        /// Withdraws the unbonded tokens of a machine after the unbonding period, i.e. the
+        /// hold on the sender's account will be released.
+        #[pallet::call_index(19)]
+        #[pallet::weight(WeightOf::<T>::withdraw_unbonded())]
+        pub fn withdraw_unbonded(origin: OriginFor<T>, machine: T::AccountId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let (machine_hash, mut bond) = Self::bond_of(&sender, &machine)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            if bond.unbonding.is_zero() || now < bond.unlock_at {
+                return Err(Error::<T>::from_mor(BondIsUnbonding));
+            }
+
+            let amount = T::Currency::release(
+                &HoldReason::MachineBond.into(),
+                &sender,
+                bond.unbonding,
+                Precision::BestEffort,
+            )?;
+            bond.unbonding = BalanceOf::<T>::zero();
+            if bond.active.is_zero() {
+                MachineBonds::<T>::remove(machine_hash);
+            } else {
+                MachineBonds::<T>::insert(machine_hash, bond);
+            }
+
+            Self::deposit_event(Event::<T>::BondWithdrawn { machine, amount });
+            Ok(())
+        }
+
+        /// Updates the configuration of staking, see `StakingConfig`.
+        #[pallet::call_index(20)]
+        #[pallet::weight(WeightOf::<T>::set_staking_config())]
+        pub fn set_staking_config(
+            origin: OriginFor<T>,
+            config: StakingConfig<BalanceOf<T>, BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            if !config.is_consistent() {
+                return Err(Error::<T>::from_mor(MorConfigIsNotConsistent));
+            }
+            StakingConfigStorage::<T>::put(&config);
+
+            Self::deposit_event(Event::<T>::StakingConfigChanged { config });
+            Ok(())
+        }
+
+        /// Reports the misbehaviour of a registered machine, e.g. spoofed heartbeats. Only
+        /// designated reporters can do this, and each machine can only have one pending
+        /// report, which awaits adjudication by governance.
+        #[pallet::call_index(21)]
+        #[pallet::weight(WeightOf::<T>::report_misbehaviour())]
+        pub fn report_misbehaviour(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            evidence: Evidence,
+        ) -> DispatchResult {
+            let reporter = T::ReporterOrigin::ensure_origin(origin)?;
+
+            let machine_hash = (machine).using_encoded(blake2_256);
+            if !MachineRegister::<T>::contains_key(machine_hash) {
+                return Err(Error::<T>::from_mor(MachineNotRegistered));
+            }
+            if PendingReports::<T>::contains_key(machine_hash) {
+                return Err(Error::<T>::from_mor(ReportAlreadyPending));
+            }
+
+            PendingReports::<T>::insert(
+                machine_hash,
+                MisbehaviourReport {
+                    machine: machine.clone(),
+                    reporter: reporter.clone(),
+                    evidence,
+                    reported_at: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::<T>::MisbehaviourReported { machine, reporter });
+            Ok(())
+        }
+
+        /// Adjudicates the pending report of a machine's misbehaviour. A confirmed offence
+        /// slashes the machine's bond into the pot, freezes its online rewards and will be
+        /// kept in its offence history. This can only be done by the admin origin.
+        #[pallet::call_index(22)]
+        #[pallet::weight(WeightOf::<T>::adjudicate_misbehaviour())]
+        pub fn adjudicate_misbehaviour(
+            origin: OriginFor<T>,
+            machine: T::AccountId,
+            confirmed: bool,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+
+            let machine_hash = (machine).using_encoded(blake2_256);
+            let report = PendingReports::<T>::take(machine_hash)
+                .ok_or_else(|| Error::<T>::from_mor(ReportNotFound))?;
+
+            if !confirmed {
+                Self::deposit_event(Event::<T>::MisbehaviourDismissed { machine });
+                return Ok(());
+            }
+
+            let bonded = MachineBonds::<T>::get(machine_hash).map_or(BalanceOf::<T>::zero(), |b| {
+                b.active.saturating_add(b.unbonding)
+            });
+            let slashed = Self::slash_bond(&machine, T::OffenceSlash::get() * bonded);
+
+            let period: BlockNumberFor<T> =
+                MorConfigStorage::<T>::get().track_n_block_rewards.into();
+            let now = <frame_system::Pallet<T>>::block_number();
+            let frozen_until =
+                now.saturating_add(period.saturating_mul(T::OffenceFreezePeriods::get().into()));
+            RewardsFrozenUntil::<T>::insert(machine_hash, frozen_until);
+
+            OffenceHistory::<T>::mutate(machine_hash, |history| {
+                if history.len() as u32 >= T::MaxOffences::get() && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(Offence {
+                    reported_at: report.reported_at,
+                    slashed,
+                    frozen_until,
+                });
+            });
+
+            Self::deposit_event(Event::<T>::MisbehaviourConfirmed {
+                machine,
+                slashed,
+                frozen_until,
+            });
+            Ok(())
+        }
+
+        /// Submits the liveness of machines, which have been pinged by the offchain worker.
+        /// Only designated reporters can do this, each reporter has one attestation per
+        /// machine and reward period. A machine's liveness changes, once `LivenessThreshold`
+        /// reporters and the majority of the period's attestations agree on it. Machines,
+        /// which have been attested offline, will not receive online rewards for the rest of
+        /// the reward period, unless they send a heartbeat. Attestations of machines, which are not registered (anymore),
+        /// will be skipped.
+        #[pallet::call_index(23)]
+        #[pallet::weight(WeightOf::<T>::submit_liveness(attestations.len() as u32))]
+        pub fn submit_liveness(
+            origin: OriginFor<T>,
+            attestations: BoundedVec<(T::AccountId, bool), T::MaxAttestations>,
+        ) -> DispatchResult {
+            let reporter = T::ReporterOrigin::ensure_origin(origin)?;
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let period = Self::current_period();
+            let mut attested = 0u32;
+            for (machine, online) in attestations {
+                let machine_hash = (machine).using_encoded(blake2_256);
+                if !MachineRegister::<T>::contains_key(machine_hash) {
+                    continue;
+                }
+                let previous = LivenessAttestations::<T>::get(machine_hash, &reporter);
+                LivenessAttestations::<T>::insert(machine_hash, &reporter, (period, online));
+                let tally = LivenessTallies::<T>::mutate(machine_hash, |tally| {
+                    tally.attest(period, previous, online);
+                    *tally
+                });
+                attested += 1;
+
+                let agreed = match tally.outcome(T::LivenessThreshold::get()) {
+                    Some(agreed) => agreed,
+                    None => continue,
+                };
+                let current = MachineLiveness::<T>::get(machine_hash);
+                if current.map_or(false, |l| {
+                    l.online == agreed && Self::period_of(l.attested_at) == period
+                }) {
+                    continue;
+                }
+                MachineLiveness::<T>::insert(
+                    machine_hash,
+                    Liveness {
+                        online: agreed,
+                        attested_at: now,
+                    },
+                );
+                Self::deposit_event(Event::<T>::LivenessAgreed {
+                    machine,
+                    online: agreed,
+                    period,
+                });
+            }
+
+            Self::deposit_event(Event::<T>::LivenessAttested {
+                reporter,
+                count: attested,
+            });
+            Ok(())
+        }
+
+        /// Submits a machine's heartbeat as unsigned transaction, so machines don't need
+        /// funds to prove their liveness. The heartbeat has to be signed by the machine's
+        /// account key for the current reward period, see `ValidateUnsigned`. Each registered
+        /// machine can send one heartbeat per period. A heartbeat proves the machine's
+        /// liveness for the period, so it will earn online rewards, even if the reporters
+        /// have attested it offline.
+        #[pallet::call_index(24)]
+        #[pallet::weight(WeightOf::<T>::submit_heartbeat())]
+        pub fn submit_heartbeat(
+            origin: OriginFor<T>,
+            heartbeat: Heartbeat<T::AccountId>,
+            // The signature has been verified by `validate_unsigned`
+            _signature: T::MachineSignature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+
+            let machine_hash = Self::check_heartbeat(&heartbeat).map_err(Error::<T>::from_mor)?;
+            LastHeartbeat::<T>::insert(machine_hash, heartbeat.period);
+
+            Self::deposit_event(Event::<T>::HeartbeatReceived {
+                machine: heartbeat.machine,
+                period: heartbeat.period,
+            });
+            Ok(())
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        /// Heartbeats are valid until the end of the reward period, they have been signed
+        /// for. Only one heartbeat per machine and period will be accepted.
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            let (heartbeat, signature) = match call {
+                Call::submit_heartbeat {
+                    heartbeat,
+                    _signature: signature,
+                } => (heartbeat, signature),
+                _ => return InvalidTransaction::Call.into(),
+            };
+
+            if heartbeat.period > Self::current_period() {
+                return InvalidTransaction::Future.into();
+            }
+            match Self::check_heartbeat(heartbeat) {
+                Ok(_) => {}
+                Err(MachineNotRegistered) => return InvalidTransaction::BadSigner.into(),
+                Err(_) => return InvalidTransaction::Stale.into(),
+            }
+            if !signature.verify(&Self::heartbeat_payload(heartbeat)[..], &heartbeat.machine) {
+                return InvalidTransaction::BadProof.into();
+            }
+
+            let period: BlockNumberFor<T> =
+                BlockNumberFor::<T>::from(MorConfigStorage::<T>::get().track_n_block_rewards)
+                    .max(One::one());
+            let now = <frame_system::Pallet<T>>::block_number();
+            let longevity: u64 = period.saturating_sub(now % period).unique_saturated_into();
+            ValidTransaction::with_tag_prefix("PeaqMorHeartbeat")
+                .priority(T::HeartbeatPriority::get())
+                .and_provides((&heartbeat.machine, heartbeat.period))
+                .longevity(longevity)
+                .propagate(true)
+                .build()
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Checks the invariants of the pallet's storages:
+        /// - the ring buffer of block-rewards has as many slots as block-rewards will be
+        ///   tracked, and the next slot to write in is one of them,
+        /// - the period reward is the sum of the ring buffer,
+        /// - the configuration is consistent,
+        /// - the reward asset is whitelisted with a reward rate,
+        /// - the accounts of each registered machine match its registration. Machines, which
+        ///   are not owned by their owner in Peaq-DID anymore, will only be flagged as stale,
+        ///   machines without accounts as unverifiable.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub(crate) fn do_try_state() -> Result<(), &'static str> {
+            let config = MorConfigStorage::<T>::get();
+            ensure!(
+                config.is_consistent(T::ExistentialDeposit::get()),
+                "MorConfig is not consistent"
+            );
+
+            let (slot_cnt, balances) = RewardsRecordStorage::<T>::get();
+            ensure!(
+                balances.len() == config.track_n_block_rewards as usize,
+                "RewardsRecordStorage does not match track_n_block_rewards"
+            );
+            ensure!(
+                (slot_cnt as usize) < balances.len(),
+                "Slot of RewardsRecordStorage is out of range"
+            );
+            ensure!(
+                PeriodRewardStorage::<T>::get() == Self::sum_of_rewards(&balances),
+                "PeriodRewardStorage is not the sum of RewardsRecordStorage"
+            );
+            if let Some(asset) = RewardAsset::<T>::get() {
+                ensure!(
+                    AcceptedAssets::<T>::get(asset).map_or(false, |c| !c.reward_rate.is_zero()),
+                    "RewardAsset is not whitelisted with a reward rate"
+                );
+            }
+
+            // Owners may transfer machines or remove their attributes in Peaq-DID, so stale
+            // registrations will only be flagged, see `reap_stale_machine`.
+            let (mut unverifiable, mut stale) = (0u32, 0u32);
+            for (machine_hash, owner_hash) in MachineRegister::<T>::iter() {
+                match MachineAccounts::<T>::get(machine_hash) {
+                    Some((machine, owner)) => {
+                        ensure!(
+                            machine.using_encoded(blake2_256) == machine_hash
+                                && owner.using_encoded(blake2_256) == owner_hash,
+                            "MachineAccounts does not match MachineRegister"
                         );
-                        return Err(MorError::UnknownError);
+                        if !T::Identity::is_owner(&owner, &machine) {
+                            stale += 1;
+                        }
                     }
-                    RewardsRecordStorage::<T>::put((slot_cnt, balances));
+                    None => unverifiable += 1,
                 }
-                _ => {}
             }
+            if unverifiable > 0 {
+                log::warn!(
+                    "{} registered machines are flagged as unverifiable",
+                    unverifiable
+                );
+            }
+            if stale > 0 {
+                log::warn!("{} registered machines are flagged as stale", stale);
+            }
+            ensure!(
+                MachineAccounts::<T>::iter_keys()
+                    .all(|hash| MachineRegister::<T>::contains_key(hash)),
+                "MachineAccounts has entries, which are not registered"
+            );
+            ensure!(
+                MachineStats::<T>::iter_keys().all(|hash| MachineRegister::<T>::contains_key(hash)),
+                "MachineStats has entries, which are not registered"
+            );
+            ensure!(
+                MorMetricsStorage::<T>::get().registered_machines as usize
+                    == MachineRegister::<T>::iter_keys().count(),
+                "MorMetrics does not match the number of registered machines"
+            );
+
             Ok(())
         }
+
+        /// Returns the sum of the tracked block-rewards, which is the period reward.
+        pub(crate) fn sum_of_rewards(balances: &[BalanceOf<T>]) -> BalanceOf<T> {
+            balances
+                .iter()
+                .fold(BalanceOf::<T>::zero(), |sum, &b| sum.saturating_add(b))
+        }
+
+        /// This method internally initialises the pallet's storages in dependency of the given MorConfig.
+        pub(crate) fn init_storages(mor_config: &MorConfig<BalanceOf<T>>) {
+            let record_list: BoundedVec<BalanceOf<T>, ConstU32<MAX_BLOCK_REWARD_NUM>> =
+                vec![BalanceOf::<T>::zero(); mor_config.track_n_block_rewards as usize]
+                    .try_into()
+                    .expect("bound checked in match arm condition; qed");
+            let reward_record = (0u8, record_list);
+
+            MorConfigStorage::<T>::put(mor_config.clone());
+            RewardsRecordStorage::<T>::put(reward_record);
+            PeriodRewardStorage::<T>::put(BalanceOf::<T>::zero());
+        }
+
+        /// Sweeps the funds above the pot's ceiling to the `SurplusDestination`. Returns the
+        /// amount of swept tokens.
+        pub(crate) fn sweep_pot() -> BalanceOf<T> {
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+
+            let surplus = T::Currency::balance(&pot).saturating_sub(T::PotCeiling::get());
+            if surplus.is_zero() {
+                return surplus;
+            }
+            match T::Currency::withdraw(
+                &pot,
+                surplus,
+                Precision::BestEffort,
+                Preservation::Preserve,
+                Fortitude::Polite,
+            ) {
+                Ok(credit) => {
+                    let swept = credit.peek();
+                    T::SurplusDestination::on_unbalanced(credit);
+                    Self::deposit_event(Event::<T>::PotSwept { amount: swept });
+                    swept
+                }
+                Err(e) => {
+                    log::error!("sweep error: {:?}", e);
+                    BalanceOf::<T>::zero()
+                }
+            }
+        }
+
+        /// Returns the index of the current reward period, see `Heartbeat`.
+        pub fn current_period() -> u32 {
+            Self::period_of(<frame_system::Pallet<T>>::block_number())
+        }
+
+        /// Returns the index of the reward period, which contains the given block.
+        pub(crate) fn period_of(block: BlockNumberFor<T>) -> u32 {
+            let period: BlockNumberFor<T> =
+                MorConfigStorage::<T>::get().track_n_block_rewards.into();
+            (block / period.max(One::one())).unique_saturated_into()
+        }
+
+        /// Checks, that the heartbeat has been signed for the current reward period by a
+        /// registered machine, which has not sent a heartbeat in this period yet. Returns the
+        /// machine's hash, which is used as storage key.
+        pub(crate) fn check_heartbeat(heartbeat: &Heartbeat<T::AccountId>) -> MorResult<[u8; 32]> {
+            let machine_hash = (heartbeat.machine).using_encoded(blake2_256);
+            if !MachineRegister::<T>::contains_key(machine_hash) {
+                return Err(MachineNotRegistered);
+            }
+            if heartbeat.period != Self::current_period() {
+                return Err(HeartbeatOutdated);
+            }
+            if LastHeartbeat::<T>::get(machine_hash).map_or(false, |p| p >= heartbeat.period) {
+                return Err(HeartbeatAlreadyReceived);
+            }
+            Ok(machine_hash)
+        }
+
+        /// Returns the payload of a heartbeat, which the machine has to sign, see
+        /// `Heartbeat::signing_payload`.
+        pub fn heartbeat_payload(heartbeat: &Heartbeat<T::AccountId>) -> Vec<u8> {
+            heartbeat.signing_payload(&<frame_system::Pallet<T>>::block_hash(
+                BlockNumberFor::<T>::zero(),
+            ))
+        }
+
+        /// Returns the metadata of a machine, see runtime API.
+        pub fn machine_metadata(machine: &T::AccountId) -> Option<MachineMetadata> {
+            MachineMetadataOf::<T>::get(machine.using_encoded(blake2_256))
+        }
+
+        /// Returns the lifetime statistics of a registered machine, see runtime API.
+        pub fn machine_stats(
+            machine: &T::AccountId,
+        ) -> Option<MachineStatistics<BalanceOf<T>, BlockNumberFor<T>>> {
+            let machine_hash = machine.using_encoded(blake2_256);
+            MachineRegister::<T>::contains_key(machine_hash)
+                .then(|| MachineStats::<T>::get(machine_hash))
+        }
+
+        /// Returns the aggregated statistics of all machines of an owner, see runtime API.
+        pub fn owner_stats(
+            owner: &T::AccountId,
+        ) -> ActivityStatistics<BalanceOf<T>, BlockNumberFor<T>> {
+            OwnerStats::<T>::get(owner.using_encoded(blake2_256))
+        }
+
+        /// Adds a donation to the block-reward, which has been logged last. Unlike
+        /// log_block_rewards, this does not advance the ring buffer, so no block-reward will
+        /// be pushed out of the tracked ones.
+        pub(crate) fn add_to_latest_block_reward(amount: BalanceOf<T>) {
+            let mor_config = MorConfigStorage::<T>::get();
+            let (slot_cnt, mut balances) = RewardsRecordStorage::<T>::get();
+            if balances.is_empty() {
+                return;
+            }
+            let latest = (slot_cnt as usize + balances.len() - 1) % balances.len();
+            balances[latest] = balances[latest].saturating_add(amount);
+
+            let period_reward = Self::sum_of_rewards(&balances);
+            RewardsRecordStorage::<T>::set((slot_cnt, balances));
+            PeriodRewardStorage::<T>::set(period_reward);
+
+            Self::update_pot_level(&mor_config);
+        }
+
+        /// Counts online and campaign rewards in the metrics. Other transfers out of the pot,
+        /// like withdrawals, campaign budgets and bounties, are not rewards.
+        pub(crate) fn note_paid_reward(amount: BalanceOf<T>) {
+            MorMetricsStorage::<T>::mutate(|metrics| {
+                metrics.paid_from_pot = metrics.paid_from_pot.saturating_add(amount)
+            });
+        }
+
+        /// Records an activity of a registered machine in its statistics and in those of its
+        /// owner. Activities of unregistered machines will not be recorded. The machine's
+        /// first activity in a period will be counted in the metrics of active machines.
+        pub(crate) fn note_activity<F>(machine: &T::AccountId, note: F)
+        where
+            F: Fn(&mut ActivityStatistics<BalanceOf<T>, BlockNumberFor<T>>, BlockNumberFor<T>),
+        {
+            let machine_hash = machine.using_encoded(blake2_256);
+            if let Ok(owner_hash) = MachineRegister::<T>::try_get(machine_hash) {
+                let now = <frame_system::Pallet<T>>::block_number();
+                let period = Self::period_of(now);
+                MachineStats::<T>::mutate(machine_hash, |stats| {
+                    if Self::period_of(stats.activity.last_active_at) != period {
+                        MorMetricsStorage::<T>::mutate(|metrics| metrics.note_active(period));
+                    }
+                    note(&mut stats.activity, now)
+                });
+                OwnerStats::<T>::mutate(owner_hash, |stats| note(stats, now));
+            }
+        }
+
+        /// Returns the protocol-wide metrics, see runtime API. The numbers of active machines
+        /// refer to the current period, even if no machine has been active in it yet.
+        pub fn mor_metrics() -> MorMetrics<BalanceOf<T>> {
+            let mut metrics = MorMetricsStorage::<T>::get();
+            metrics.roll_to(Self::current_period());
+            metrics
+        }
+
+        /// Returns the account of a campaign's pot, which is a sub-account of the pallet's pot.
+        pub(crate) fn campaign_account(campaign_id: CampaignId) -> T::AccountId {
+            T::PotId::get().into_sub_account_truncating(campaign_id)
+        }
+
+        /// Checks, whether a machine's document in Peaq-DID contains a valid (non-expired)
+        /// attribute with the given name and, if given, with the given value.
+        pub(crate) fn has_valid_attribute(
+            machine: &T::AccountId,
+            name: &[u8],
+            value: Option<&[u8]>,
+        ) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+            T::Identity::attribute_of(machine, name).map_or(false, |attr| {
+                now < attr.validity && value.map_or(true, |v| attr.value[..] == v[..])
+            })
+        }
+
+        /// Checks, whether a machine fulfils the eligibility rules of a campaign.
+        pub(crate) fn is_eligible(
+            machine: &T::AccountId,
+            eligibility: &CampaignEligibility,
+        ) -> bool {
+            match eligibility {
+                CampaignEligibility::AllMachines => true,
+                CampaignEligibility::DidAttribute(name) => {
+                    Self::has_valid_attribute(machine, name, None)
+                }
+                CampaignEligibility::MachineClass(name, value) => {
+                    Self::has_valid_attribute(machine, name, Some(&value[..]))
+                }
+            }
+        }
+
+        /// Checks, whether a machine fulfils all eligibility rules, which have been
+        /// configured by governance.
+        pub(crate) fn check_eligibility(machine: &T::AccountId) -> MorResult<()> {
+            let eligible = MachineEligibilityRules::<T>::get().iter().all(|r| {
+                Self::has_valid_attribute(machine, &r.name, r.value.as_ref().map(|v| &v[..]))
+            });
+            if eligible {
+                Ok(())
+            } else {
+                Err(MachineNotEligible)
+            }
+        }
+
+        /// Checks, whether a registered machine may earn rewards right now. This applies to
+        /// online rewards and campaign rewards alike.
+        pub(crate) fn check_earning(
+            machine_hash: [u8; 32],
+            machine: &T::AccountId,
+        ) -> MorResult<()> {
+            // Machines, whose attributes have expired, stop earning automatically
+            Self::check_eligibility(machine)?;
+            // Machines, whose misbehaviour has been confirmed, stop earning for a while
+            let now = <frame_system::Pallet<T>>::block_number();
+            if RewardsFrozenUntil::<T>::get(machine_hash).map_or(false, |until| now < until) {
+                return Err(MachineRewardsFrozen);
+            }
+            // Machines, which have been attested offline, stop earning for the rest of the
+            // reward period, in which they have been attested. A heartbeat of the machine in
+            // that period proves its liveness though.
+            let period = Self::period_of(now);
+            if MachineLiveness::<T>::get(machine_hash).map_or(false, |l| {
+                !l.online && Self::period_of(l.attested_at) == period
+            }) && LastHeartbeat::<T>::get(machine_hash) != Some(period)
+            {
+                return Err(MachineOffline);
+            }
+            Ok(())
+        }
+
+        /// Checks, that the machine is eligible for a reward of the campaign right now and
+        /// books the claim. Returns the amount, which has to be paid out of the campaign's pot.
+        pub(crate) fn claim_campaign(
+            owner: &T::AccountId,
+            campaign_id: CampaignId,
+            machine: &T::AccountId,
+        ) -> MorResult<BalanceOf<T>> {
+            let machine_hash = Self::check_machine_owner(owner, machine)?;
+            let mut campaign = Campaigns::<T>::get(campaign_id).ok_or(CampaignNotFound)?;
+            Self::check_earning(machine_hash, machine)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if !campaign.is_active(now) {
+                return Err(CampaignNotActive);
+            }
+            if !Self::is_eligible(machine, &campaign.eligibility) {
+                return Err(MachineNotEligible);
+            }
+            if let Some(last_claim) = CampaignClaims::<T>::get(campaign_id, machine_hash) {
+                if now < last_claim.saturating_add(campaign.claim_interval) {
+                    return Err(CampaignRewardAlreadyClaimed);
+                }
+            }
+            let paid = campaign.paid.saturating_add(campaign.reward_rate);
+            if paid > campaign.budget {
+                return Err(CampaignBudgetExhausted);
+            }
+
+            campaign.paid = paid;
+            let reward = campaign.reward_rate;
+            Campaigns::<T>::insert(campaign_id, campaign);
+            CampaignClaims::<T>::insert(campaign_id, machine_hash, now);
+            Ok(reward)
+        }
+
+        /// Returns the amount of tokens in the pot, which can be transfered without touching
+        /// the reserve floor of the MorConfig and without reaping the pot's account.
+        pub(crate) fn pot_available(config: &MorConfig<BalanceOf<T>>) -> BalanceOf<T> {
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+
+            let reducible =
+                T::Currency::reducible_balance(&pot, Preservation::Preserve, Fortitude::Polite);
+            let above_reserve = T::Currency::balance(&pot).saturating_sub(config.pot_reserve);
+            reducible.min(above_reserve)
+        }
+
+        /// Scales down the given online reward, if the pot runs low. Below the threshold of
+        /// the MorConfig the reward will be scaled by the ratio of the available amount to
+        /// the threshold, and it will never exceed the available amount.
+        pub(crate) fn scale_to_pot(reward: BalanceOf<T>) -> BalanceOf<T> {
+            let config = MorConfigStorage::<T>::get();
+            Self::scale_to_available(
+                reward,
+                Self::pot_available(&config),
+                config.pot_low_threshold,
+            )
+        }
+
+        /// Scales down the given online reward in the reward asset like `scale_to_pot`. The
+        /// reserve floor and the threshold of the MorConfig will be converted by the asset's
+        /// reward rate.
+        pub(crate) fn scale_asset_to_pot(
+            asset: AssetIdOf<T>,
+            asset_config: &AssetConfig<BalanceOf<T>>,
+            reward: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            let config = MorConfigStorage::<T>::get();
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+
+            let reducible = T::Assets::reducible_balance(
+                asset.clone(),
+                &pot,
+                Preservation::Preserve,
+                Fortitude::Polite,
+            );
+            let above_reserve = T::Assets::balance(asset, &pot)
+                .saturating_sub(asset_config.convert(config.pot_reserve));
+            Self::scale_to_available(
+                reward,
+                reducible.min(above_reserve),
+                asset_config.convert(config.pot_low_threshold),
+            )
+        }
+
+        /// Scales down the given reward by the ratio of the available amount to the
+        /// threshold, if the available amount is below it. Never exceeds the available amount.
+        fn scale_to_available(
+            reward: BalanceOf<T>,
+            available: BalanceOf<T>,
+            threshold: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            let reward = if available < threshold {
+                Perbill::from_rational(available, threshold) * reward
+            } else {
+                reward
+            };
+            reward.min(available)
+        }
+
+        /// Checks, whether the pot's balance has crossed the threshold of the MorConfig and
+        /// announces, when the pot is running low.
+        pub(crate) fn update_pot_level(config: &MorConfig<BalanceOf<T>>) {
+            let available = Self::pot_available(config);
+            let is_low = available < config.pot_low_threshold;
+
+            if is_low != PotIsLow::<T>::get() {
+                PotIsLow::<T>::put(is_low);
+                if is_low {
+                    Self::deposit_event(Event::<T>::PotLow { available });
+                }
+            }
+        }
+
+        /// Returns the index of the current minting period and the amount of tokens, which
+        /// have already been minted within that period.
+        pub(crate) fn minted_in_current_period(
+            config: &MorConfig<BalanceOf<T>>,
+        ) -> (BlockNumberFor<T>, BalanceOf<T>) {
+            let now = frame_system::Pallet::<T>::block_number();
+            let period = now / BlockNumberFor::<T>::from(config.mint_period.max(1));
+
+            let (last_period, minted) = MintedInPeriod::<T>::get();
+            if last_period == period {
+                (period, minted)
+            } else {
+                (period, BalanceOf::<T>::zero())
+            }
+        }
+
+        /// Checks, that the machine is registered in Peaq-DID and Peaq-MOR and that it is owned
+        /// by the given account. Returns the machine's hash, which is used as storage key.
+        pub(crate) fn check_machine_owner(
+            owner: &T::AccountId,
+            machine: &T::AccountId,
+        ) -> MorResult<[u8; 32]> {
+            // Is still registered in the identity provider (Peaq-DID) and is this the owner?
+            if !T::Identity::is_owner(owner, machine) {
+                return Err(DidAuthorizationFailed);
+            }
+            // Is machine registered in Peaq-MOR?
+            let machine_hash = (machine).using_encoded(blake2_256);
+            if !MachineRegister::<T>::contains_key(machine_hash) {
+                return Err(MorError::MachineNotRegistered);
+            }
+            let owner_hash = MachineRegister::<T>::get(machine_hash);
+            if owner_hash != (owner).using_encoded(blake2_256) {
+                return Err(MorError::MorAuthorizationFailed);
+            }
+
+            Ok(machine_hash)
+        }
+
+        /// Returns the bond of a machine, if it belongs to the given owner.
+        #[allow(clippy::type_complexity)]
+        pub(crate) fn bond_of(
+            owner: &T::AccountId,
+            machine: &T::AccountId,
+        ) -> Result<([u8; 32], MachineBondOf<T>), DispatchError> {
+            let machine_hash = (machine).using_encoded(blake2_256);
+            match MachineBonds::<T>::get(machine_hash) {
+                Some(bond) if bond.owner == *owner => Ok((machine_hash, bond)),
+                _ => Err(Error::<T>::from_mor(MachineNotBonded)),
+            }
+        }
+
+        /// Returns the bonus on top of the given base reward, which the machine earns for
+        /// the tokens bonded behind it by its current owner.
+        pub(crate) fn stake_bonus(
+            owner: &T::AccountId,
+            machine: &T::AccountId,
+            reward: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            match MachineBonds::<T>::get(machine.using_encoded(blake2_256)) {
+                Some(bond) if bond.owner == *owner => {
+                    StakingConfigStorage::<T>::get().bonus(reward, bond.active)
+                }
+                _ => BalanceOf::<T>::zero(),
+            }
+        }
+
+        /// Slashes up to the given amount of the tokens bonded behind a machine into the pot,
+        /// e.g. when the machine has misbehaved. Active tokens will be slashed first, then
+        /// unbonding ones. Returns the slashed amount.
+        pub fn slash_bond(machine: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+            let machine_hash = (machine).using_encoded(blake2_256);
+            let mut bond = match MachineBonds::<T>::get(machine_hash) {
+                Some(b) => b,
+                None => return BalanceOf::<T>::zero(),
+            };
+
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+            let amount = amount.min(bond.active.saturating_add(bond.unbonding));
+            let slashed = T::Currency::transfer_on_hold(
+                &HoldReason::MachineBond.into(),
+                &bond.owner,
+                &pot,
+                amount,
+                Precision::BestEffort,
+                Restriction::Free,
+                Fortitude::Force,
+            )
+            .unwrap_or_else(|_| BalanceOf::<T>::zero());
+
+            let from_active = slashed.min(bond.active);
+            bond.active -= from_active;
+            bond.unbonding = bond.unbonding.saturating_sub(slashed - from_active);
+            if bond.active.is_zero() && bond.unbonding.is_zero() {
+                MachineBonds::<T>::remove(machine_hash);
+            } else {
+                MachineBonds::<T>::insert(machine_hash, bond);
+            }
+
+            if !slashed.is_zero() {
+                Self::deposit_event(Event::<T>::BondSlashed {
+                    machine: machine.clone(),
+                    amount: slashed,
+                });
+            }
+            slashed
+        }
+
+        /// Removes the registration of a machine, everything its owner has published and
+        /// everything, which has been recorded about it, e.g. reports and offences.
+        pub(crate) fn remove_machine(machine_hash: [u8; 32]) {
+            if MachineRegister::<T>::contains_key(machine_hash) {
+                MorMetricsStorage::<T>::mutate(|metrics| {
+                    metrics.registered_machines = metrics.registered_machines.saturating_sub(1)
+                });
+            }
+            MachineRegister::<T>::remove(machine_hash);
+            MachineAccounts::<T>::remove(machine_hash);
+            MachineTariffs::<T>::remove(machine_hash);
+            MachineMetadataOf::<T>::remove(machine_hash);
+            MachineLiveness::<T>::remove(machine_hash);
+            LivenessTallies::<T>::remove(machine_hash);
+            // Bounded, so the weight of `reap_stale_machine` covers it. Attestations, which
+            // remain after more reporters have attested the machine, are of former periods
+            // and will not be counted towards a tally of a later period.
+            let _ = LivenessAttestations::<T>::clear_prefix(
+                machine_hash,
+                T::MaxAttestations::get(),
+                None,
+            );
+            LastHeartbeat::<T>::remove(machine_hash);
+            FeelessCallsUsed::<T>::remove(machine_hash);
+            MachineStats::<T>::remove(machine_hash);
+            PendingReports::<T>::remove(machine_hash);
+            OffenceHistory::<T>::remove(machine_hash);
+            RewardsFrozenUntil::<T>::remove(machine_hash);
+            Self::release_bond(machine_hash);
+        }
+
+        /// Releases all tokens, which have been bonded behind a machine, to the owner of the
+        /// bond and removes it, e.g. when the machine's registration has been re-assigned.
+        pub(crate) fn release_bond(machine_hash: [u8; 32]) {
+            if let Some(bond) = MachineBonds::<T>::take(machine_hash) {
+                let _ = T::Currency::release(
+                    &HoldReason::MachineBond.into(),
+                    &bond.owner,
+                    bond.active.saturating_add(bond.unbonding),
+                    Precision::BestEffort,
+                );
+            }
+        }
+
+        /// Returns the limits of machine usage fees for the given currency, either from the
+        /// MorConfig or from the whitelist of assets.
+        pub(crate) fn usage_fee_range(
+            currency: &TariffCurrency<AssetIdOf<T>>,
+        ) -> MorResult<(BalanceOf<T>, BalanceOf<T>)> {
+            match currency {
+                TariffCurrency::Native => {
+                    let config = MorConfigStorage::<T>::get();
+                    Ok((config.machine_usage_fee_min, config.machine_usage_fee_max))
+                }
+                TariffCurrency::Asset(asset) => {
+                    let config = AcceptedAssets::<T>::get(asset).ok_or(AssetNotAccepted)?;
+                    Ok((config.machine_usage_fee_min, config.machine_usage_fee_max))
+                }
+            }
+        }
+
+        /// Checks, that a machine usage payment is within the configured limits of the given
+        /// currency, and that it matches the machine's tariff, if there is one. Returns the flat
+        /// fee of the tariff, which is included in the amount, or zero without a tariff.
+        pub(crate) fn check_usage_payment(
+            machine: &T::AccountId,
+            currency: &TariffCurrency<AssetIdOf<T>>,
+            amount: BalanceOf<T>,
+        ) -> MorResult<BalanceOf<T>> {
+            let (fee_min, fee_max) = Self::usage_fee_range(currency)?;
+            if fee_min > amount || amount > fee_max {
+                return Err(MachinePaymentOutOfRange);
+            }
+
+            match MachineTariffs::<T>::get(machine.using_encoded(blake2_256)) {
+                Some(t) if &t.currency != currency || !t.accepts(amount) => {
+                    Err(MachinePaymentDoesNotMatchTariff)
+                }
+                Some(t) => Ok(t.flat_fee),
+                None => Ok(BalanceOf::<T>::zero()),
+            }
+        }
+    }
+
+    // See MorBalance trait definition for further details
+    impl<T: Config> MorBalance<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn mint_to_account(
+            account: &T::AccountId,
+            amount: BalanceOf<T>,
+            reason: MintReason,
+        ) -> DispatchResult {
+            // Check the caps of minting, before anything will be minted
+            let config = MorConfigStorage::<T>::get();
+            let (period, minted_in_period) = Self::minted_in_current_period(&config);
+            let emission = TotalEmission::<T>::get();
+            if emission.saturating_add(amount) > config.emission_budget {
+                return Err(Error::<T>::from_mor(EmissionBudgetExhausted));
+            }
+            if minted_in_period.saturating_add(amount) > config.max_mint_per_period {
+                return Err(Error::<T>::from_mor(MintPeriodCapReached));
+            }
+
+            // Check in advance, whether the deposit would be dust or would overflow the
+            // total issuance, so nothing will be minted partially
+            T::Currency::can_deposit(account, amount, Provenance::Minted)
+                .into_result()
+                .map_err(|_| Error::<T>::from_mor(TokensCouldNotBeTransfered))?;
+
+            let issuance = T::Currency::total_issuance();
+            let minted = T::Currency::mint_into(account, amount)
+                .map_err(|_| Error::<T>::from_mor(TokensCouldNotBeTransfered))?;
+
+            // Defensive: total issuance has to be increased by exactly the minted amount
+            if minted != amount || T::Currency::total_issuance() != issuance + minted {
+                log::error!("mint error: minted: {:?}, amount: {:?}", minted, amount);
+                return Err(Error::<T>::from_mor(UnknownError));
+            }
+
+            let minted_in_period = minted_in_period.saturating_add(minted);
+            let emission = emission.saturating_add(minted);
+            MintedInPeriod::<T>::put((period, minted_in_period));
+            TotalEmission::<T>::put(emission);
+            MorMetricsStorage::<T>::mutate(|metrics| metrics.note_minted(minted, reason));
+
+            Self::deposit_event(Event::<T>::MintedTokens {
+                account: account.clone(),
+                amount: minted,
+                remaining_in_period: config.max_mint_per_period.saturating_sub(minted_in_period),
+                remaining_budget: config.emission_budget.saturating_sub(emission),
+            });
+            Ok(())
+        }
+
+        fn transfer_from_pot(account: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+            let config = MorConfigStorage::<T>::get();
+
+            if Self::pot_available(&config) >= amount {
+                T::Currency::transfer(&pot, account, amount, Preservation::Preserve)?;
+                Self::update_pot_level(&config);
+                Ok(())
+            } else {
+                Err(Error::<T>::from_mor(InsufficientTokensInPot))
+            }
+        }
+
+        fn log_block_rewards(amount: BalanceOf<T>) {
+            let mor_config = MorConfigStorage::<T>::get();
+            let n_blocks = mor_config.track_n_block_rewards;
+
+            // RewardsRecordStorage: (u8, Vec<BalanceOf<T>>)
+            // (Next array-slot to write in, vector of imbalances)
+            let (mut slot_cnt, mut balances) = RewardsRecordStorage::<T>::get();
+            balances[slot_cnt as usize] = amount;
+            slot_cnt += 1;
+            if slot_cnt >= n_blocks {
+                slot_cnt = 0;
+            }
+
+            // PeriodRewardStorage: BalanceOf<T>
+            // Sum of last n_blocks block-rewards
+            let period_reward = Self::sum_of_rewards(&balances);
+
+            RewardsRecordStorage::<T>::set((slot_cnt, balances));
+            PeriodRewardStorage::<T>::set(period_reward);
+
+            Self::update_pot_level(&mor_config);
+        }
+
+        // Use more defensive approach here, because this method is called by the runtime
+        // But the Vec to boundedVec conversion shouldn't fail, because the Vec is created by boundedVec
+        fn resize_track_storage(new_size: u8) -> MorResult<()> {
+            let new_size = new_size as usize;
+            let (slot_cnt, balances) = RewardsRecordStorage::<T>::get();
+            let cur_size = balances.len();
+            if cur_size == new_size {
+                return Ok(());
+            }
+
+            // Bring the block-rewards into chronological order, the oldest one first. The
+            // next slot to write in always holds the oldest block-reward.
+            let mut balances = balances.to_vec();
+            if cur_size > 0 {
+                balances.rotate_left(slot_cnt as usize % cur_size);
+            }
+            // Keep the most recent block-rewards, a grown window starts with empty slots.
+            if cur_size > new_size {
+                balances.drain(..cur_size - new_size);
+            } else {
+                let mut grown = vec![BalanceOf::<T>::zero(); new_size - cur_size];
+                grown.append(&mut balances);
+                balances = grown;
+            }
+
+            let balances: BoundedVec<BalanceOf<T>, ConstU32<MAX_BLOCK_REWARD_NUM>> =
+                balances.try_into().map_err(|_| {
+                    log::error!("conversion error");
+                    MorError::UnknownError
+                })?;
+            if balances.len() != new_size {
+                log::error!(
+                    "resize error: len: {}, new_size: {}",
+                    balances.len(),
+                    new_size
+                );
+                return Err(MorError::UnknownError);
+            }
+            // The oldest block-reward is in the first slot now, so it will be overwritten next.
+            PeriodRewardStorage::<T>::put(Self::sum_of_rewards(&balances));
+            RewardsRecordStorage::<T>::put((0u8, balances));
+            Ok(())
+        }
+    }
+
+    // See MorAssets trait definition for further details
+    impl<T: Config> MorAssets<T::AccountId, AssetIdOf<T>, BalanceOf<T>> for Pallet<T> {
+        fn transfer_asset(
+            asset: AssetIdOf<T>,
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::Assets::transfer(asset, from, to, amount, Preservation::Expendable)?;
+            Ok(())
+        }
+
+        fn transfer_asset_from_pot(
+            asset: AssetIdOf<T>,
+            account: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let pot: T::AccountId = T::PotId::get().into_account_truncating();
+
+            let reducible = T::Assets::reducible_balance(
+                asset.clone(),
+                &pot,
+                Preservation::Preserve,
+                Fortitude::Polite,
+            );
+            if reducible >= amount {
+                T::Assets::transfer(asset, &pot, account, amount, Preservation::Preserve)?;
+                Ok(())
+            } else {
+                Err(Error::<T>::from_mor(InsufficientTokensInPot))
+            }
+        }
     }
 
     // See MorMachine trait description for further details
@@ -572,8 +2685,11 @@ pub mod pallet {
             owner: &T::AccountId,
             machine: &T::AccountId,
         ) -> MorResult<BalanceOf<T>> {
-            // Registered in Peaq-DID and is this the owner?
-            DidPallet::<T>::is_owner(owner, machine).map_err(MorError::from)?;
+            // Registered in the identity provider (Peaq-DID) and is this the owner?
+            if !T::Identity::is_owner(owner, machine) {
+                return Err(DidAuthorizationFailed);
+            }
+            Self::check_eligibility(machine)?;
 
             let machine_hash = (machine).using_encoded(blake2_256);
             if MachineRegister::<T>::contains_key(machine_hash) {
@@ -581,26 +2697,120 @@ pub mod pallet {
             } else {
                 let owner_hash = (owner).using_encoded(blake2_256);
                 let config = MorConfigStorage::<T>::get();
+                // The registration reward will only be paid once per machine, also if it has
+                // been removed and registered again in between
+                let reward = MachineTombstones::<T>::mutate(machine_hash, |tombstone| {
+                    if tombstone.registration_rewarded {
+                        BalanceOf::<T>::zero()
+                    } else {
+                        tombstone.registration_rewarded = true;
+                        config.registration_reward
+                    }
+                });
                 MachineRegister::<T>::insert(machine_hash, owner_hash);
+                MachineAccounts::<T>::insert(machine_hash, (machine.clone(), owner.clone()));
+                let now = <frame_system::Pallet<T>>::block_number();
+                MachineStats::<T>::insert(
+                    machine_hash,
+                    MachineStatistics {
+                        registered_at: now,
+                        activity: ActivityStatistics {
+                            last_active_at: now,
+                            ..Default::default()
+                        },
+                    },
+                );
+                MorMetricsStorage::<T>::mutate(|metrics| {
+                    metrics.registered_machines = metrics.registered_machines.saturating_add(1);
+                    metrics.note_active(Self::period_of(now));
+                });
                 // 1 AGNG = 1_000_000_000_000_000_000
-                Ok(config.registration_reward)
+                Ok(reward)
             }
         }
 
         fn reward_machine(owner: &T::AccountId, machine: &T::AccountId) -> MorResult<BalanceOf<T>> {
-            // Is still registered in Peaq-DID and is this the owner?
-            DidPallet::<T>::is_owner(owner, machine).map_err(MorError::from)?;
-            // Is machine registered in Peaq-MOR?
-            let machine_hash = (machine).using_encoded(blake2_256);
-            if !MachineRegister::<T>::contains_key(machine_hash) {
-                return Err(MorError::MachineNotRegistered);
-            }
-            let owner_hash = MachineRegister::<T>::get(machine_hash);
-            if owner_hash != (owner).using_encoded(blake2_256) {
-                return Err(MorError::MorAuthorizationFailed);
-            }
+            let machine_hash = Self::check_machine_owner(owner, machine)?;
+            Self::check_earning(machine_hash, machine)?;
+
+            // The period reward will be split by stake: the base reward and its bonus never
+            // exceed the period reward.
+            let period_reward = PeriodRewardStorage::<T>::get();
+            let reward = StakingConfigStorage::<T>::get().base_reward(period_reward);
+            Ok(reward
+                .saturating_add(Self::stake_bonus(owner, machine, reward))
+                .min(period_reward))
+        }
+    }
+
+    // See MorInterface trait description for further details
+    impl<T: Config> MorInterface<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        fn is_registered(machine: &T::AccountId) -> bool {
+            MachineRegister::<T>::contains_key(machine.using_encoded(blake2_256))
+        }
+
+        fn owner_of(machine: &T::AccountId) -> Option<T::AccountId> {
+            MachineAccounts::<T>::get(machine.using_encoded(blake2_256)).map(|(_, owner)| owner)
+        }
 
-            Ok(PeriodRewardStorage::<T>::get())
+        fn register_on_behalf(
+            owner: &T::AccountId,
+            machine: &T::AccountId,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            // Other pallets may call this outside of a transactional extrinsic, so the
+            // registration has to be reverted, if minting the reward fails
+            let reward = with_storage_layer(|| -> Result<BalanceOf<T>, DispatchError> {
+                let reward =
+                    Self::register_machine(owner, machine).map_err(Error::<T>::from_mor)?;
+                if !reward.is_zero() {
+                    Self::mint_to_account(owner, reward, MintReason::Registration)?;
+                }
+                Ok(reward)
+            })?;
+
+            Self::deposit_event(Event::<T>::MachineRegistered {
+                owner: owner.clone(),
+                machine: machine.clone(),
+                reward,
+            });
+            Ok(reward)
+        }
+
+        fn reward_machine_from(
+            source: &T::AccountId,
+            machine: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let owner = Self::owner_of(machine)
+                .ok_or_else(|| Error::<T>::from_mor(MachineNotRegistered))?;
+            Self::check_machine_owner(&owner, machine).map_err(Error::<T>::from_mor)?;
+
+            T::Currency::transfer(source, &owner, amount, Preservation::Preserve)?;
+            Self::note_activity(machine, |stats, now| stats.note_reward(amount, false, now));
+            Self::deposit_event(Event::<T>::MachineRewarded {
+                source: source.clone(),
+                machine: machine.clone(),
+                amount,
+            });
+            Ok(())
+        }
+
+        fn record_usage_payment(
+            payer: &T::AccountId,
+            machine: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let fee = Self::check_usage_payment(machine, &TariffCurrency::Native, amount)
+                .map_err(Error::<T>::from_mor)?;
+            Self::note_activity(machine, |stats, now| stats.note_usage_payment(amount, now));
+
+            Self::deposit_event(Event::<T>::UsagePaid {
+                payer: payer.clone(),
+                machine: machine.clone(),
+                amount,
+                fee,
+            });
+            Ok(())
         }
     }
 }
@@ -1,55 +1,306 @@
 //! Storage migrations for the peaq-pallet-mor.
+//!
+//! Each migration is an `OnRuntimeUpgrade` type, which only runs on the storage version it
+//! has been written for and bumps the storage version by one. `Migrations` chains all of
+//! them in order, so a chain on any older version will be migrated step by step.
+//!
+//! The pallet's `on_runtime_upgrade` hook runs `Migrations`. To run the `pre_upgrade` and
+//! `post_upgrade` checks with try-runtime, add `Migrations<Runtime>` to the migrations of
+//! the runtime's `Executive`, the hook will then find nothing left to do.
 
-use frame_support::{pallet_prelude::*, weights::Weight};
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade, weights::Weight};
+use sp_runtime::traits::Zero;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
 
 use crate::{
     pallet::*,
-    types::{BalanceOf, MorConfig},
+    types::{BalanceOf, MachineTombstone, MorConfig, MorMetrics},
 };
 
+/// All migrations of this pallet, in the order they have to be applied.
+pub type Migrations<T> = (v3::MigrateToV3<T>, v4::MigrateToV4<T>);
+
 pub(crate) fn on_runtime_upgrade<T: Config>() -> Weight {
-    v2::MigrateToV2x::<T>::on_runtime_upgrade()
+    Migrations::<T>::on_runtime_upgrade()
+}
+
+/// Checks the integrity of the ring buffer of block-rewards, which has to contain as many
+/// slots as block-rewards will be tracked, and the next slot to write in has to be one of
+/// them.
+#[cfg(feature = "try-runtime")]
+fn check_reward_record<T: Config>(track_n_block_rewards: u8) -> Result<(), &'static str> {
+    let (slot_cnt, balances) = RewardsRecordStorage::<T>::get();
+    ensure!(
+        balances.len() == track_n_block_rewards as usize,
+        "RewardsRecordStorage does not match track_n_block_rewards"
+    );
+    ensure!(
+        (slot_cnt as usize) < balances.len(),
+        "Slot of RewardsRecordStorage is out of range"
+    );
+    Ok(())
+}
+
+/// Reads the MorConfig in the layout up to version 3. Returns an error, if the stored
+/// configuration cannot be decoded in that layout.
+fn old_mor_config<T: Config>() -> Result<Option<v4::OldMorConfig<BalanceOf<T>>>, &'static str> {
+    let key = MorConfigStorage::<T>::hashed_key();
+    match frame_support::storage::unhashed::get_raw(&key) {
+        Some(raw) => v4::OldMorConfig::<BalanceOf<T>>::decode(&mut &raw[..])
+            .map(Some)
+            .map_err(|_| "MorConfig has an unexpected layout"),
+        None => Ok(None),
+    }
+}
+
+pub mod v3 {
+    use super::*;
+    use sp_std::vec;
+
+    /// Migration implementation for all versions before 3. Older versions did not ensure,
+    /// that the ring buffer of block-rewards matches the tracked number of block-rewards.
+    /// If it does not, the ring buffer will be rebuilt, but the configuration will be kept.
+    pub struct MigrateToV3<T>(sp_std::marker::PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+            if on_chain_version < 3 {
+                log::info!(
+                    "Migrating storage from version {:?} to version 3",
+                    on_chain_version
+                );
+                let track_n_block_rewards = match old_mor_config::<T>() {
+                    Ok(Some(config)) => config.track_n_block_rewards,
+                    Ok(None) => MorConfig::<BalanceOf<T>>::default().track_n_block_rewards,
+                    Err(e) => {
+                        log::error!("{}, resetting it to the default", e);
+                        let default = MorConfig::<BalanceOf<T>>::default();
+                        frame_support::storage::unhashed::put(
+                            &MorConfigStorage::<T>::hashed_key(),
+                            &v4::OldMorConfig::from(default.clone()),
+                        );
+                        default.track_n_block_rewards
+                    }
+                };
+
+                let (_slot_cnt, balances) = RewardsRecordStorage::<T>::get();
+                if balances.len() != track_n_block_rewards as usize {
+                    log::info!("Rebuilding RewardsRecordStorage");
+                    RewardsRecordStorage::<T>::mutate(|(slot_cnt, balances)| {
+                        *slot_cnt = 0;
+                        *balances = BoundedVec::truncate_from(vec![
+                            BalanceOf::<T>::zero();
+                            track_n_block_rewards as usize
+                        ]);
+                    });
+                    PeriodRewardStorage::<T>::put(BalanceOf::<T>::zero());
+                }
+                StorageVersion::new(3).put::<Pallet<T>>();
+                T::DbWeight::get().reads_writes(3, 4)
+            } else {
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            if Pallet::<T>::on_chain_storage_version() >= 3 {
+                return Ok(Vec::new());
+            }
+            let key = MorConfigStorage::<T>::hashed_key();
+            Ok(frame_support::storage::unhashed::get_raw(&key).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            if state.is_empty() {
+                return Ok(());
+            }
+            let old_raw = Option::<Vec<u8>>::decode(&mut &state[..])
+                .map_err(|_| "Invalid state of pre_upgrade")?;
+            let key = MorConfigStorage::<T>::hashed_key();
+            let decodable = old_raw.as_ref().map_or(true, |raw| {
+                v4::OldMorConfig::<BalanceOf<T>>::decode(&mut &raw[..]).is_ok()
+            });
+            if decodable {
+                ensure!(
+                    frame_support::storage::unhashed::get_raw(&key) == old_raw,
+                    "MorConfig has not been preserved"
+                );
+            } else {
+                ensure!(
+                    old_mor_config::<T>()? == Some(MorConfig::<BalanceOf<T>>::default().into()),
+                    "MorConfig has not been reset to the default"
+                );
+            }
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "Storage version has not been updated"
+            );
+            let track_n_block_rewards = old_mor_config::<T>()?
+                .map(|config| config.track_n_block_rewards)
+                .unwrap_or(MorConfig::<BalanceOf<T>>::default().track_n_block_rewards);
+            check_reward_record::<T>(track_n_block_rewards)
+        }
+    }
 }
 
-mod v2 {
+pub mod v4 {
     use super::*;
+    use frame_support::traits::tokens::Balance as BalanceT;
+    use sp_runtime::traits::Bounded;
 
-    /// Migration implementation that renames storage HardCap into MaxCurrencySupply
-    pub struct MigrateToV2x<T>(sp_std::marker::PhantomData<T>);
+    /// Layout of MorConfig up to version 3, the last released one.
+    #[derive(Encode, Decode, PartialEq)]
+    pub(crate) struct OldMorConfig<Balance: BalanceT> {
+        #[codec(compact)]
+        pub(crate) registration_reward: Balance,
+        #[codec(compact)]
+        pub(crate) machine_usage_fee_min: Balance,
+        #[codec(compact)]
+        pub(crate) machine_usage_fee_max: Balance,
+        #[codec(compact)]
+        pub(crate) track_n_block_rewards: u8,
+    }
+
+    impl<Balance: BalanceT> From<MorConfig<Balance>> for OldMorConfig<Balance> {
+        fn from(config: MorConfig<Balance>) -> Self {
+            OldMorConfig {
+                registration_reward: config.registration_reward,
+                machine_usage_fee_min: config.machine_usage_fee_min,
+                machine_usage_fee_max: config.machine_usage_fee_max,
+                track_n_block_rewards: config.track_n_block_rewards,
+            }
+        }
+    }
 
-    impl<T: Config> MigrateToV2x<T> {
-        pub fn on_runtime_upgrade() -> Weight {
-            let mut weight_reads = 1;
-            let mut weight_writes = 0;
+    /// Migration implementation from the released version 3 to the current layout.
+    ///
+    /// - The MorConfig will be extended by the minting caps and the pot's reserve. To keep
+    ///   the behaviour of the pallet, minting will not be capped and no reserve will be kept,
+    ///   until the configuration has been updated. The minting period will be the tracked
+    ///   period of block-rewards.
+    /// - The protocol-wide metrics will be introduced. The number of registered machines
+    ///   will be counted, all amounts start at zero.
+    /// - Registered machines have been paid their registration reward, so their tombstones
+    ///   will record it.
+    ///
+    /// Up to version 3 the pallet has been bound to `ReservableCurrency` and
+    /// `LockableCurrency`, but it never reserved or locked any funds itself, so there is
+    /// nothing to translate into holds. All other storages have been introduced after
+    /// version 3 and start empty.
+    pub struct MigrateToV4<T>(sp_std::marker::PhantomData<T>);
 
-            let this_version = Pallet::<T>::current_storage_version();
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
             let on_chain_version = Pallet::<T>::on_chain_storage_version();
 
-            if on_chain_version < this_version {
+            if on_chain_version == 3 {
                 log::info!(
-                    "Migrating storage from version {:?} to version {:?}",
-                    on_chain_version,
-                    this_version
+                    "Migrating storage from version {:?} to version 4",
+                    on_chain_version
                 );
-                // Translate the reward_rec
-                let reward_rec = RewardsRecordStorage::<T>::get();
-                let mor_config = MorConfigStorage::<T>::get();
-                // This part, we only need to check whether the data is set before
-                // Therefore, once it not setup, we should reset it
-                if mor_config.track_n_block_rewards as usize != reward_rec.1.len() {
-                    log::info!("Resetting storage");
-                    let mor_config = MorConfig::<BalanceOf<T>>::default();
-                    Pallet::<T>::init_storages(&mor_config);
-                    weight_writes += 3;
-                    weight_reads += 2;
-                    T::DbWeight::get().reads_writes(2, 3);
-                } else {
-                    weight_reads += 2;
-                    T::DbWeight::get().reads_writes(2, 0);
+                let res =
+                    MorConfigStorage::<T>::translate::<OldMorConfig<BalanceOf<T>>, _>(|old| {
+                        old.map(|old| MorConfig {
+                            registration_reward: old.registration_reward,
+                            machine_usage_fee_min: old.machine_usage_fee_min,
+                            machine_usage_fee_max: old.machine_usage_fee_max,
+                            track_n_block_rewards: old.track_n_block_rewards,
+                            max_mint_per_period: BalanceOf::<T>::max_value(),
+                            mint_period: old.track_n_block_rewards.into(),
+                            emission_budget: BalanceOf::<T>::max_value(),
+                            pot_reserve: Zero::zero(),
+                            pot_low_threshold: Zero::zero(),
+                        })
+                    });
+                // A failed translation leaves the undecodable value in place, so the
+                // default has to be put explicitly.
+                if res.is_err() {
+                    log::error!("MorConfig could not be translated, resetting it to the default");
+                    MorConfigStorage::<T>::put(MorConfig::<BalanceOf<T>>::default());
                 }
-                STORAGE_VERSION.put::<Pallet<T>>();
+
+                let mut registered = 0u32;
+                for machine_hash in MachineRegister::<T>::iter_keys() {
+                    MachineTombstones::<T>::insert(
+                        machine_hash,
+                        MachineTombstone {
+                            registration_rewarded: true,
+                            bounty_paid: false,
+                        },
+                    );
+                    registered += 1;
+                }
+                MorMetricsStorage::<T>::put(MorMetrics {
+                    registered_machines: registered,
+                    period: Pallet::<T>::current_period(),
+                    ..Default::default()
+                });
+                StorageVersion::new(4).put::<Pallet<T>>();
+                T::DbWeight::get()
+                    .reads_writes(u64::from(registered) + 3, u64::from(registered) + 3)
+            } else {
+                T::DbWeight::get().reads(1)
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            if Pallet::<T>::on_chain_storage_version() != 3 {
+                return Ok(Vec::new());
+            }
+            let count = MachineRegister::<T>::iter_keys().count() as u32;
+            Ok(Some((old_mor_config::<T>()?, count)).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            if state.is_empty() {
+                return Ok(());
+            }
+            let (old, count) =
+                Option::<(Option<OldMorConfig<BalanceOf<T>>>, u32)>::decode(&mut &state[..])
+                    .map_err(|_| "Invalid state of pre_upgrade")?
+                    .ok_or("Invalid state of pre_upgrade")?;
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 4,
+                "Storage version has not been updated"
+            );
+            let new = MorConfigStorage::<T>::get();
+            if let Some(old) = old {
+                ensure!(
+                    new.registration_reward == old.registration_reward
+                        && new.machine_usage_fee_min == old.machine_usage_fee_min
+                        && new.machine_usage_fee_max == old.machine_usage_fee_max
+                        && new.track_n_block_rewards == old.track_n_block_rewards,
+                    "MorConfig has not been preserved"
+                );
+                ensure!(
+                    new.max_mint_per_period == BalanceOf::<T>::max_value()
+                        && new.emission_budget == BalanceOf::<T>::max_value()
+                        && new.mint_period == u32::from(old.track_n_block_rewards),
+                    "Minting caps have not been initialised"
+                );
+                ensure!(
+                    new.pot_reserve.is_zero() && new.pot_low_threshold.is_zero(),
+                    "Pot reserve has not been initialised"
+                );
             }
-            T::DbWeight::get().reads_writes(weight_reads, weight_writes)
+            ensure!(
+                MorMetricsStorage::<T>::get().registered_machines == count,
+                "Registered machines have not been counted"
+            );
+            ensure!(
+                MachineRegister::<T>::iter_keys().all(|machine_hash| {
+                    MachineTombstones::<T>::get(machine_hash).registration_rewarded
+                }),
+                "Registration rewards have not been recorded"
+            );
+            check_reward_record::<T>(new.track_n_block_rewards)
         }
     }
 }
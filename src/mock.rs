@@ -2,29 +2,44 @@
 use crate as peaq_pallet_mor;
 pub use crate::{
     mock_const::*,
-    types::{BalanceOf, MorConfig},
+    types::{BalanceOf, HoldReason, MorConfig},
 };
 
 use frame_benchmarking::account;
 #[cfg(feature = "std")]
 use frame_support::traits::GenesisBuild;
-use frame_support::{construct_runtime, parameter_types, PalletId};
-use frame_system;
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, ConstU8, Contains},
+    weights::IdentityFee,
+    PalletId,
+};
+use frame_system::{
+    self,
+    offchain::{AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes},
+    EnsureRoot, EnsureSigned,
+};
 use pallet_balances;
 use pallet_timestamp;
 use sp_core::{sr25519, H256};
 use sp_io;
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
-    testing::Header,
-    traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+    testing::{Header, TestXt},
+    traits::{AccountIdConversion, BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+    Perbill,
 };
 use sp_std::{boxed::Box, vec, vec::Vec};
 
 // system
 pub type Block = frame_system::mocking::MockBlock<Test>;
 pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+// offchain worker
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
 // pallet-balances
 pub type BalancesType = u128;
+// pallet-assets
+pub type AssetIdType = u32;
 
 // Configure a mock runtime to test the pallet.
 construct_runtime!(
@@ -37,8 +52,9 @@ construct_runtime!(
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
         Balances: pallet_balances::{Pallet, Call, Config<T>, Storage, Event<T>},
-        PeaqDid: peaq_pallet_did::{Pallet, Call, Storage, Event<T>},
-        PeaqMor: peaq_pallet_mor::{Pallet, Call, Config<T>, Storage, Event<T>},
+        TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>},
+        Assets: pallet_assets::{Pallet, Call, Config<T>, Storage, Event<T>},
+        PeaqMor: peaq_pallet_mor::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
     }
 );
 
@@ -50,6 +66,10 @@ parameter_types! {
     pub const MinimumPeriod: u64 = 5;
     // peaq-pallet-mor
     pub const PotId: PalletId = PalletId(*b"PotMchOw");
+    pub const PotCeiling: u128 = POT_CEILING;
+    pub const ReapBounty: u128 = REAP_BOUNTY;
+    pub const OffenceSlash: Perbill = Perbill::from_percent(50);
+    pub static LivenessThreshold: u32 = 1;
     // pallet_balances
     pub const ExistentialDeposit: u128 = 500;
     pub const MaxLocks: u32 = 50;
@@ -105,23 +125,103 @@ impl pallet_balances::Config for Test {
     type ExistentialDeposit = ExistentialDeposit;
     type AccountStore = System;
     type WeightInfo = ();
-    type HoldIdentifier = ();
-    type FreezeIdentifier = ();
-    type MaxHolds = ();
-    type MaxFreezes = ();
+    type HoldIdentifier = HoldReason;
+    type FreezeIdentifier = HoldReason;
+    type MaxHolds = ConstU32<1>;
+    type MaxFreezes = ConstU32<1>;
+}
+
+impl pallet_transaction_payment::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+    type OperationalFeeMultiplier = ConstU8<5>;
+    type WeightToFee = IdentityFee<BalancesType>;
+    type LengthToFee = IdentityFee<BalancesType>;
+    type FeeMultiplierUpdate = ();
 }
 
-impl peaq_pallet_did::Config for Test {
+impl pallet_assets::Config for Test {
     type RuntimeEvent = RuntimeEvent;
-    type Time = pallet_timestamp::Pallet<Test>;
-    type WeightInfo = peaq_pallet_did::weights::WeightInfo<Test>;
+    type Balance = BalancesType;
+    type AssetId = AssetIdType;
+    type AssetIdParameter = parity_scale_codec::Compact<AssetIdType>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<10>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+impl SigningTypes for Test {
+    type Public = sr25519::Public;
+    type Signature = sr25519::Signature;
+}
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        _public: Self::Public,
+        _account: Self::AccountId,
+        nonce: Self::Index,
+    ) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+        Some((call, (nonce, ())))
+    }
+}
+
+/// Machines report their usage without fees.
+pub struct MachineFeelessCalls;
+impl Contains<peaq_pallet_mor::Call<Test>> for MachineFeelessCalls {
+    fn contains(call: &peaq_pallet_mor::Call<Test>) -> bool {
+        matches!(call, peaq_pallet_mor::Call::pay_machine_usage { .. })
+    }
 }
 
 impl peaq_pallet_mor::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ExistentialDeposit = ExistentialDeposit;
     type Currency = Balances;
+    type RuntimeHoldReason = HoldReason;
+    type Assets = Assets;
     type PotId = PotId;
+    type AdminOrigin = EnsureRoot<Self::AccountId>;
+    type PotCeiling = PotCeiling;
+    type SurplusDestination = ();
+    type ReapBounty = ReapBounty;
+    type ReporterOrigin = EnsureSigned<Self::AccountId>;
+    type OffenceSlash = OffenceSlash;
+    type OffenceFreezePeriods = ConstU32<2>;
+    type MaxOffences = ConstU32<3>;
+    type Identity = peaq_pallet_mor::identity::InMemoryIdentity<sr25519::Public, u64>;
+    type AuthorityId = peaq_pallet_mor::offchain::crypto::AuthId;
+    type MaxAttestations = ConstU32<16>;
+    type LivenessThreshold = LivenessThreshold;
+    type MachineSignature = sr25519::Signature;
+    type MachinePublic = sr25519::Public;
+    type HeartbeatPriority = ConstU64<{ u64::MAX / 2 }>;
+    type MachineFeelessCalls = MachineFeelessCalls;
+    type MachineFeeQuota = ConstU32<2>;
     type WeightInfo = peaq_pallet_mor::weights::WeightInfo<Test>;
 }
 
@@ -156,18 +256,39 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .assimilate_storage(&mut test_ext)
     .unwrap();
 
+    pallet_assets::GenesisConfig::<Test> {
+        assets: vec![(ASSET_ID, owner, true, ASSET_MIN_BALANCE)],
+        metadata: vec![],
+        accounts: vec![
+            (ASSET_ID, user, 10_000_000_000),
+            (ASSET_ID, mor_pot, 10_000_000_000),
+        ],
+    }
+    .assimilate_storage(&mut test_ext)
+    .unwrap();
+
     peaq_pallet_mor::GenesisConfig::<Test> {
         mor_config: MorConfig {
             registration_reward: BalanceOf::<Test>::from(REG_FEE),
             machine_usage_fee_min: BalanceOf::<Test>::from(100_000_000_000_000_000u128),
             machine_usage_fee_max: BalanceOf::<Test>::from(3_000_000_000_000_000_000u128),
             track_n_block_rewards: 10u8,
+            max_mint_per_period: BalanceOf::<Test>::from(MINT_CAP),
+            mint_period: 10u32,
+            emission_budget: BalanceOf::<Test>::from(EMISSION_BUDGET),
+            pot_reserve: BalanceOf::<Test>::from(0u128),
+            pot_low_threshold: BalanceOf::<Test>::from(0u128),
         },
     }
     .assimilate_storage(&mut test_ext)
     .unwrap();
 
-    test_ext.into()
+    // Events will only be deposited from block number one on
+    let mut test_ext: sp_io::TestExternalities = test_ext.into();
+    test_ext.execute_with(|| System::set_block_number(1));
+    // Benchmarks generate the keys of machines, which sign heartbeats, in the keystore
+    test_ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+    test_ext
 }
 
 #[allow(dead_code)]
@@ -13,3 +13,23 @@ pub const M_ATTR: &[u8] = b"Type";
 pub const M_VAL: &[u8] = b"RPiMachine";
 /// Typical registration reward
 pub const REG_FEE: u128 = 100_000_000_000_000_000u128;
+/// Typical maximum amount of minted tokens per minting period
+pub const MINT_CAP: u128 = 100_000_000_000_000_000_000u128;
+/// Typical lifetime emission budget
+pub const EMISSION_BUDGET: u128 = 10_000_000_000_000_000_000_000u128;
+/// Typical ceiling of the pot
+pub const POT_CEILING: u128 = 20_000_000_000_000_000_000u128;
+/// Generic whitelisted asset (e.g. a stablecoin)
+pub const ASSET_ID: u32 = 1;
+/// Minimum balance of the generic asset
+pub const ASSET_MIN_BALANCE: u128 = 10;
+/// Generic name of a reward campaign
+pub const CAMPAIGN_NAME: &[u8] = b"Pilot";
+/// Location of the generic machine as geohash
+pub const M_GEOHASH: &[u8] = b"u33dc0cpke7v";
+/// Firmware version of the generic machine
+pub const M_FIRMWARE: &[u8] = b"v1.0.0";
+/// Typical bounty for reaping a stale registration
+pub const REAP_BOUNTY: u128 = 10_000_000_000_000_000u128;
+/// Endpoint of the generic machine, which will be pinged by the offchain worker
+pub const M_ENDPOINT: &[u8] = b"https://rpi001.example.com/health";
@@ -1,16 +1,21 @@
 //! In this module are all traits of the pallet defined. These traits define the core
 //! behaviour of the pallet and its functionality.
 
-use frame_support::pallet_prelude::DispatchResult;
+use frame_support::pallet_prelude::{DispatchError, DispatchResult};
 
-use crate::error::MorResult;
+use crate::{
+    error::MorResult,
+    types::{MachineAttribute, MintReason},
+};
 
 /// The trait `MorBalance` describes relevant functionality related to tokens. If
 /// tokens will be minted or transfered from the pot is implemented here. Also a
 /// method to track the collected block-rewards is listed here.
 pub trait MorBalance<AccountId, Balance> {
-    /// Core function to mint new tokens and transfer them to a given account.
-    fn mint_to_account(account: &AccountId, amount: Balance) -> DispatchResult;
+    /// Core function to mint new tokens and transfer them to a given account. Fails without
+    /// minting anything, if the deposit would be below the existential deposit. The reason
+    /// will be counted in the pallet's metrics.
+    fn mint_to_account(account: &AccountId, amount: Balance, reason: MintReason) -> DispatchResult;
 
     /// Core function to transfer tokens from the pallet's pot to a given account.
     fn transfer_from_pot(account: &AccountId, amount: Balance) -> DispatchResult;
@@ -22,9 +27,32 @@ pub trait MorBalance<AccountId, Balance> {
 
     /// When the configuration of the pallet will be changed, the storage size changes
     /// too. This method will reorganize the storage of the pallet and adapt its content.
+    /// The most recent block-rewards will be kept in chronological order and the period
+    /// reward will be recomputed.
     fn resize_track_storage(new_size: u8) -> MorResult<()>;
 }
 
+/// The trait `MorAssets` describes functionality related to fungible assets besides the
+/// native currency, e.g. stablecoins, which can be used to pay for machine usage or to
+/// pay out online rewards.
+pub trait MorAssets<AccountId, AssetId, Balance> {
+    /// Core function to transfer assets from one account to another, e.g. from the user
+    /// of a machine to the machine itself.
+    fn transfer_asset(
+        asset: AssetId,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
+
+    /// Core function to transfer assets from the pallet's pot to a given account.
+    fn transfer_asset_from_pot(
+        asset: AssetId,
+        account: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
+}
+
 /// The trait `MorMachine` encapsules adminstrative methods related to machines.
 pub trait MorMachine<AccountId, Balance> {
     /// Internal registration of a machine, to track which machines have been registered
@@ -35,3 +63,65 @@ pub trait MorMachine<AccountId, Balance> {
     /// Internal functionality to be used by the dispatchable method.
     fn reward_machine(owner: &AccountId, machine: &AccountId) -> MorResult<Balance>;
 }
+
+/// The trait `MorInterface` lets other pallets of the runtime, e.g. staking or a
+/// marketplace, compose the functionality of Peaq-MOR without going through extrinsics.
+/// Callers have to account for the weight of these methods, which is close to the weight
+/// of the corresponding extrinsics, see `WeightInfo`.
+pub trait MorInterface<AccountId, Balance> {
+    /// Checks whether the machine is registered in Peaq-MOR.
+    fn is_registered(machine: &AccountId) -> bool;
+
+    /// Returns the owner, who has registered the machine in Peaq-MOR.
+    fn owner_of(machine: &AccountId) -> Option<AccountId>;
+
+    /// Registers a machine on behalf of its owner, who will get the registration reward
+    /// like by `get_registration_reward`. Returns the paid reward. If the reward cannot be
+    /// minted, the machine will not be registered either.
+    fn register_on_behalf(owner: &AccountId, machine: &AccountId)
+        -> Result<Balance, DispatchError>;
+
+    /// Transfers a reward from the given account to the owner of a registered machine,
+    /// e.g. from the account of a staking pallet.
+    fn reward_machine_from(
+        source: &AccountId,
+        machine: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
+
+    /// Records a usage payment of a machine, which has been settled by another pallet, e.g.
+    /// a marketplace. The amount will be checked like by `pay_machine_usage`, but no tokens
+    /// will be minted.
+    fn record_usage_payment(
+        payer: &AccountId,
+        machine: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult;
+}
+
+/// The trait `MachineIdentityProvider` describes, where machines and their owners are
+/// registered, e.g. in Peaq-DID. It decouples this pallet from a certain identity pallet,
+/// see module `identity` for its implementations.
+pub trait MachineIdentityProvider<AccountId> {
+    /// Type of block numbers, in which the validity of attributes is given.
+    type BlockNumber;
+
+    /// Checks whether the given account owns the machine's identity.
+    fn is_owner(owner: &AccountId, machine: &AccountId) -> bool;
+
+    /// Returns the current owner of the machine's identity, if there is any and the
+    /// provider can look it up. Providers, which can only confirm an owner, return `None`,
+    /// so callers must not treat `None` as proof that the identity has no owner.
+    fn owner_of(machine: &AccountId) -> Option<AccountId>;
+
+    /// Returns the attribute with the given name of the machine's identity.
+    fn attribute_of(
+        machine: &AccountId,
+        name: &[u8],
+    ) -> Option<MachineAttribute<Self::BlockNumber>>;
+
+    /// Adds an attribute, which is valid forever, to the machine's identity on behalf of
+    /// its owner. This is only needed to set up benchmarks.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn add_attribute(owner: &AccountId, machine: &AccountId, name: &[u8], value: &[u8]);
+}
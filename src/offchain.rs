@@ -0,0 +1,124 @@
+//! Offchain worker, which verifies the liveness of machines. At the start of each reward
+//! period (see `MorConfig::track_n_block_rewards`) it pings the endpoints, which have been
+//! published in the machines' metadata, and submits the results as signed transactions
+//! (see `submit_liveness`). The transactions will be signed by the keys of type `KEY_TYPE`
+//! in the node's keystore, their accounts have to be accepted by the `ReporterOrigin`.
+
+use frame_support::BoundedVec;
+use frame_system::offchain::{SendSignedTransaction, Signer};
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+    offchain::{http, Duration},
+    traits::Zero,
+};
+use sp_std::vec::Vec;
+
+use crate::{Call, Config, MachineAccounts, MachineMetadataOf, MorConfigStorage, Pallet};
+
+/// Key type of the keys, which sign the liveness attestations.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"mor!");
+
+/// Time in milliseconds, which all machines' endpoints have to respond in. All requests
+/// will be sent at once and share this deadline.
+const PING_TIMEOUT_MS: u64 = 2_000;
+
+/// Keys of the offchain worker, see `Config::AuthorityId`.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// Identifier of the keys, which sign the liveness attestations.
+    pub struct AuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = Sr25519Signature;
+        type GenericPublic = Sr25519Public;
+    }
+
+    // Runtimes, whose accounts are plain sr25519 keys, e.g. for tests.
+    impl frame_system::offchain::AppCrypto<Sr25519Public, Sr25519Signature> for AuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = Sr25519Signature;
+        type GenericPublic = Sr25519Public;
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Pings the endpoints of all machines at the start of a reward period and submits
+    /// the results, in batches of `MaxAttestations`.
+    pub(crate) fn attest_liveness(now: T::BlockNumber) {
+        let period: T::BlockNumber = MorConfigStorage::<T>::get().track_n_block_rewards.into();
+        if period.is_zero() || !(now % period).is_zero() {
+            return;
+        }
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        if !signer.can_sign() {
+            log::debug!("no keys to attest the liveness of machines");
+            return;
+        }
+
+        let (machines, endpoints): (Vec<T::AccountId>, Vec<_>) = MachineMetadataOf::<T>::iter()
+            .filter(|(_, metadata)| !metadata.endpoint.is_empty())
+            .filter_map(|(machine_hash, metadata)| {
+                MachineAccounts::<T>::get(machine_hash)
+                    .map(|(machine, _)| (machine, metadata.endpoint))
+            })
+            .unzip();
+        let attestations: Vec<(T::AccountId, bool)> = machines
+            .into_iter()
+            .zip(Self::ping_endpoints(&endpoints))
+            .collect();
+
+        let max = (T::MaxAttestations::get() as usize).max(1);
+        for batch in attestations.chunks(max) {
+            let attestations: BoundedVec<_, T::MaxAttestations> = match batch.to_vec().try_into() {
+                Ok(a) => a,
+                Err(_) => return,
+            };
+            match signer.send_signed_transaction(|_| Call::submit_liveness {
+                attestations: attestations.clone(),
+            }) {
+                Some((_, Ok(()))) => {}
+                Some((account, Err(()))) => {
+                    log::error!("liveness could not be submitted by {:?}", account.id)
+                }
+                None => log::error!("liveness could not be submitted"),
+            }
+        }
+    }
+
+    /// Sends GET requests to the machines' endpoints and waits for all of them once, until
+    /// a shared deadline. A machine is online, if its endpoint responds with a success
+    /// status in time. Returns the results in the order of the endpoints.
+    pub(crate) fn ping_endpoints<E: AsRef<[u8]>>(endpoints: &[E]) -> Vec<bool> {
+        let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(PING_TIMEOUT_MS));
+        let pending: Vec<Option<http::PendingRequest>> = endpoints
+            .iter()
+            .map(|endpoint| {
+                let url = sp_std::str::from_utf8(endpoint.as_ref()).ok()?;
+                http::Request::get(url).deadline(deadline).send().ok()
+            })
+            .collect();
+
+        let (indices, requests): (Vec<usize>, Vec<http::PendingRequest>) = pending
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, request)| request.map(|request| (i, request)))
+            .unzip();
+        let mut online = sp_std::vec![false; endpoints.len()];
+        for (i, response) in indices
+            .into_iter()
+            .zip(http::PendingRequest::try_wait_all(requests, deadline))
+        {
+            online[i] = matches!(response, Ok(Ok(response)) if (200..300).contains(&response.code));
+        }
+        online
+    }
+}
@@ -0,0 +1,33 @@
+//! Runtime API definition of the Peaq-MOR pallet, so explorers and apps can read the
+//! pallet's state without decoding its storage.
+
+use parity_scale_codec::Codec;
+
+use crate::types::{ActivityStatistics, MachineMetadata, MachineStatistics, MorMetrics};
+
+sp_api::decl_runtime_apis! {
+    /// The runtime API of Peaq-MOR. Version 2 adds the statistics of machines and owners,
+    /// version 3 the protocol-wide metrics.
+    #[api_version(3)]
+    pub trait PeaqMorApi<AccountId, Balance, BlockNumber>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns the metadata of a machine, if its owner has published it.
+        fn machine_metadata(machine: AccountId) -> Option<MachineMetadata>;
+
+        /// Returns the lifetime statistics of a machine, if it is registered.
+        #[api_version(2)]
+        fn machine_stats(machine: AccountId) -> Option<MachineStatistics<Balance, BlockNumber>>;
+
+        /// Returns the aggregated statistics of all machines, which an owner has had.
+        #[api_version(2)]
+        fn owner_stats(owner: AccountId) -> ActivityStatistics<Balance, BlockNumber>;
+
+        /// Returns the protocol-wide metrics, e.g. for tokenomics dashboards.
+        #[api_version(3)]
+        fn mor_metrics() -> MorMetrics<Balance>;
+    }
+}
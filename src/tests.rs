@@ -1,25 +1,83 @@
 //! Unit tests for this pallet, see spec definition
 
 use crate::{
+    extension::CheckMorMachineFee,
+    identity::InMemoryIdentity,
+    migrations::Migrations,
     mock::*,
-    mor::MorBalance,
-    types::{BalanceOf, MorConfig},
-    Error,
+    mor::{MachineIdentityProvider, MorBalance, MorInterface},
+    offchain::KEY_TYPE,
+    types::{
+        ActivityStatistics, AssetConfig, AssetIdOf, BalanceOf, CampaignEligibility,
+        EligibilityRule, EligibilityRules, Evidence, Heartbeat, MachineAttribute, MachineBond,
+        MachineMetadata, MachineStatistics, MachineTariff, MintReason, MorConfig, StakeCurve,
+        StakingConfig, TariffCurrency,
+    },
+    Error, MachineAccounts, MachineBonds, MachineRegister, MorConfigStorage, MorMetricsStorage,
+    PeriodRewardStorage, RewardsRecordStorage,
 };
-use frame_support::{assert_noop, assert_ok};
-use sp_core::sr25519::Public;
-use sp_runtime::traits::BadOrigin;
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchInfo,
+    storage::unhashed,
+    traits::{
+        fungible::{InspectHold, MutateHold},
+        ConstU32, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+    },
+    weights::Weight,
+    BoundedVec,
+};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use parity_scale_codec::{Compact, Decode, Encode};
+use proptest::prelude::*;
+use sp_core::{
+    offchain::{
+        testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+        OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+    },
+    sr25519::{self, Public},
+    Pair,
+};
+use sp_io::hashing::blake2_256;
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{
+    traits::{
+        AccountIdConversion, BadOrigin, Dispatchable, SignedExtension, ValidateUnsigned, Zero,
+    },
+    transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+    FixedPointNumber, FixedU128, Perbill,
+};
+use std::collections::VecDeque;
+
+fn machine_hash(machine: Public) -> [u8; 32] {
+    machine.using_encoded(blake2_256)
+}
+
+type Identity = InMemoryIdentity<Public, u64>;
+
+fn add_machine_attribute(
+    owner: Public,
+    machine: Public,
+    name: &[u8],
+    value: &[u8],
+    validity: Option<u64>,
+) {
+    // Like Peaq-DID, the owner's identity is set along with the attribute, which is
+    // valid for the given number of blocks.
+    Identity::set_owner(&machine, Some(&owner));
+    Identity::set_attribute(
+        &machine,
+        name,
+        Some(MachineAttribute {
+            value: value.to_vec(),
+            validity: validity.map_or(u64::MAX, |v| System::block_number() + v),
+        }),
+    );
+}
 
 fn register_machine_did(owner: Public, machine: Public) {
-    // Register at least one attribute on Peaq-DID.
-    // Expect no error.
-    assert_ok!(PeaqDid::add_attribute(
-        RuntimeOrigin::signed(owner),
-        machine,
-        M_ATTR.to_vec(),
-        M_VAL.to_vec(),
-        None
-    ));
+    // Register at least one attribute of the machine's identity.
+    add_machine_attribute(owner, machine, M_ATTR, M_VAL, None);
 }
 
 fn get_registration_reward_mor(owner: Public, machine: Public) {
@@ -42,6 +100,11 @@ fn def_config(
         machine_usage_fee_min,
         machine_usage_fee_max,
         track_n_block_rewards,
+        max_mint_per_period: BalanceOf::<Test>::from(MINT_CAP),
+        mint_period: 10u32,
+        emission_budget: BalanceOf::<Test>::from(EMISSION_BUDGET),
+        pot_reserve: BalanceOf::<Test>::from(0u128),
+        pot_low_threshold: BalanceOf::<Test>::from(0u128),
     }
 }
 
@@ -63,6 +126,39 @@ fn register_new_machine_test() {
     });
 }
 
+#[test]
+fn machine_identity_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let attribute = MachineAttribute {
+            value: M_VAL.to_vec(),
+            validity: 5u64,
+        };
+
+        // Set the owner and an attribute of a machine's identity in memory.
+        assert!(!Identity::is_owner(&owner, &machine));
+        Identity::set_owner(&machine, Some(&owner));
+        Identity::set_attribute(&machine, M_ATTR, Some(attribute.clone()));
+        assert!(Identity::is_owner(&owner, &machine));
+        assert_eq!(Identity::owner_of(&machine), Some(owner));
+        assert_eq!(Identity::attribute_of(&machine, M_ATTR), Some(attribute));
+        assert_eq!(Identity::attribute_of(&machine, b"Firmware"), None);
+
+        // Remove them again.
+        Identity::set_owner(&machine, None);
+        Identity::set_attribute(&machine, M_ATTR, None);
+        assert!(!Identity::is_owner(&owner, &machine));
+        assert_eq!(Identity::attribute_of(&machine, M_ATTR), None);
+
+        // The mock runtime looks up identities in memory, so Peaq-MOR accepts the
+        // machine, once its owner has been set.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_eq!(PeaqMor::owner_of(&machine), Some(owner));
+    });
+}
+
 #[test]
 fn get_online_rewards_test() {
     new_test_ext().execute_with(|| {
@@ -99,108 +195,2864 @@ fn get_online_rewards_test() {
 }
 
 #[test]
-fn pay_machine_usage_test() {
+fn mor_interface_test() {
     new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
         let muser = account_key(U_ACCT);
         let machine = account_key(M_ACCT);
         let amount = BalanceOf::<Test>::from(500_000_000_000_000_000u128);
-        let amount_oor = BalanceOf::<Test>::from(5_000_000_000_000_000_000u128);
 
-        // Try to pay for machine usage.
+        // Try to reward a machine, which is not registered in Peaq-MOR.
+        // Expect error MachineNotRegistered.
+        assert!(!PeaqMor::is_registered(&machine));
+        assert_eq!(PeaqMor::owner_of(&machine), None);
+        assert_noop!(
+            PeaqMor::reward_machine_from(&muser, &machine, amount),
+            Error::<Test>::MachineNotRegistered
+        );
+
+        // Register the machine on behalf of its owner.
+        // Expect no error.
+        register_machine_did(owner, machine);
+        let balance = Balances::free_balance(owner);
+        assert_eq!(PeaqMor::register_on_behalf(&owner, &machine), Ok(REG_FEE));
+        System::assert_last_event(
+            crate::Event::<Test>::MachineRegistered {
+                owner,
+                machine,
+                reward: REG_FEE,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(owner), balance + REG_FEE);
+        assert!(PeaqMor::is_registered(&machine));
+        assert_eq!(PeaqMor::owner_of(&machine), Some(owner));
+
+        // Reward the machine's owner from another account.
+        // Expect no error.
+        let balance = Balances::free_balance(owner);
+        assert_ok!(PeaqMor::reward_machine_from(&muser, &machine, amount));
+        System::assert_last_event(
+            crate::Event::<Test>::MachineRewarded {
+                source: muser,
+                machine,
+                amount,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(owner), balance + amount);
+
+        // Record a usage payment, which is out of the configured range.
+        // Expect error MachinePaymentOutOfRange.
+        assert_noop!(
+            PeaqMor::record_usage_payment(&muser, &machine, 1u128),
+            Error::<Test>::MachinePaymentOutOfRange
+        );
+
+        // Record a usage payment, no tokens will be minted.
+        // Expect no error.
+        let issuance = Balances::total_issuance();
+        assert_ok!(PeaqMor::record_usage_payment(&muser, &machine, amount));
+        System::assert_last_event(
+            crate::Event::<Test>::UsagePaid {
+                payer: muser,
+                machine,
+                amount,
+                fee: 0,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::total_issuance(), issuance);
+    });
+}
+
+#[test]
+fn register_on_behalf_mint_fails_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+
+        // Leave no emission budget for the registration reward.
+        let mut config = def_config(
+            BalanceOf::<Test>::from(REG_FEE),
+            BalanceOf::<Test>::from(REG_FEE),
+            BalanceOf::<Test>::from(2 * REG_FEE),
+            10,
+        );
+        config.emission_budget = BalanceOf::<Test>::from(REG_FEE - 1);
+        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+
+        // Register the machine on behalf of its owner, minting the reward fails.
+        // Expect error EmissionBudgetExhausted and nothing to be changed.
+        register_machine_did(owner, machine);
+        assert_noop!(
+            PeaqMor::register_on_behalf(&owner, &machine),
+            Error::<Test>::EmissionBudgetExhausted
+        );
+        assert!(!PeaqMor::is_registered(&machine));
+        assert_eq!(PeaqMor::owner_of(&machine), None);
+        assert!(!PeaqMor::machine_tombstone_of(machine_hash(machine)).registration_rewarded);
+        assert_eq!(PeaqMor::mor_metrics().registered_machines, 0);
+    });
+}
+
+#[test]
+fn machine_stats_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let reward = 1_000_000_000_000_000_000u128;
+        let amount = 500_000_000_000_000_000u128;
+
+        // Payments to unregistered machines will not be recorded.
         // Expect no error.
         assert_ok!(PeaqMor::pay_machine_usage(
             RuntimeOrigin::signed(muser),
             machine,
             amount
         ));
+        assert_eq!(PeaqMor::machine_stats(&machine), None);
+        assert_eq!(PeaqMor::owner_stats(&owner), ActivityStatistics::default());
 
-        // Try to pay out of range.
-        // Expect error MachinePaymentOutOfRange.
-        assert_noop!(
-            PeaqMor::pay_machine_usage(RuntimeOrigin::signed(muser), machine, amount_oor),
-            Error::<Test>::MachinePaymentOutOfRange
+        // Registration starts the machine's statistics.
+        // Expect no error.
+        System::set_block_number(2);
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        let mut stats = MachineStatistics {
+            registered_at: 2,
+            activity: ActivityStatistics {
+                last_active_at: 2,
+                ..Default::default()
+            },
+        };
+        assert_eq!(PeaqMor::machine_stats(&machine), Some(stats.clone()));
+
+        // Claim online rewards, get rewarded by another pallet and get paid for usage
+        // directly and by another pallet.
+        // Expect no error.
+        System::set_block_number(3);
+        PeaqMor::log_block_rewards(reward);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        assert_ok!(PeaqMor::reward_machine_from(&muser, &machine, amount));
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            amount
+        ));
+        assert_ok!(PeaqMor::record_usage_payment(&muser, &machine, amount));
+        stats.activity = ActivityStatistics {
+            online_rewards: reward + amount,
+            reward_claims: 1,
+            usage_revenue: 2 * amount,
+            usage_payments: 2,
+            last_active_at: 3,
+        };
+        assert_eq!(PeaqMor::machine_stats(&machine), Some(stats.clone()));
+        assert_eq!(PeaqMor::owner_stats(&owner), stats.activity);
+
+        // Removing the machine removes its statistics, but not those of its owner.
+        PeaqMor::remove_machine(machine_hash(machine));
+        assert_eq!(PeaqMor::machine_stats(&machine), None);
+        assert_eq!(
+            PeaqMor::machine_stats_of(machine_hash(machine)),
+            Default::default()
         );
+        assert_eq!(PeaqMor::owner_stats(&owner), stats.activity);
     });
 }
 
 #[test]
-fn set_configuration_test() {
+fn mor_metrics_test() {
     new_test_ext().execute_with(|| {
-        let b_too_low = BalanceOf::<Test>::from(250u128);
-        let b_low = BalanceOf::<Test>::from(100_000_000_000_000u128);
-        let b_med = BalanceOf::<Test>::from(500_000_000_000_000u128);
-        let b_max = BalanceOf::<Test>::from(2_500_000_000_000_000u128);
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let other = account_key("RPi002");
+        let reward = 1_000_000_000_000_000_000u128;
+        let amount = 500_000_000_000_000_000u128;
 
-        // Try to set invalid configuration (range of balances).
-        // Expect error MorConfigIsNotConsistent.
-        let config = def_config(b_low, b_med, b_med, 50);
-        assert_noop!(
-            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
-            Error::<Test>::MorConfigIsNotConsistent
+        // Register two machines in the first period, both count as active.
+        // Expect no error.
+        for m in [machine, other] {
+            register_machine_did(owner, m);
+            get_registration_reward_mor(owner, m);
+        }
+        let metrics = PeaqMor::mor_metrics();
+        assert_eq!(metrics.minted_for_registration, 2 * REG_FEE);
+        assert_eq!(
+            (metrics.registered_machines, metrics.active_machines),
+            (2, 2)
         );
 
-        // Try to set invalid configuration (number of blocks).
-        // Expect error MorConfigIsNotConsistent.
-        let config = def_config(b_low, b_med, b_med, 0);
+        // In the next period, one machine is paid twice and the other one is rewarded.
+        // Payments to unregistered machines are minted, but do not count as active.
+        // Expect no error.
+        System::set_block_number(11);
+        assert_eq!(PeaqMor::mor_metrics().active_machines, 0);
+        for m in [machine, machine, account_key("RPi003")] {
+            assert_ok!(PeaqMor::pay_machine_usage(
+                RuntimeOrigin::signed(muser),
+                m,
+                amount
+            ));
+        }
+        PeaqMor::log_block_rewards(reward);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            other
+        ));
+        let metrics = PeaqMor::mor_metrics();
+        assert_eq!(metrics.minted_for_usage, 3 * amount);
+        assert_eq!(metrics.paid_from_pot, reward);
+        assert_eq!(
+            (
+                metrics.period,
+                metrics.active_machines,
+                metrics.previous_active_machines
+            ),
+            (1, 2, 2)
+        );
+
+        // Skip a period and remove a machine.
+        // Expect no active machines in the current and in the previous period.
+        System::set_block_number(31);
+        PeaqMor::remove_machine(machine_hash(machine));
+        let metrics = PeaqMor::mor_metrics();
+        assert_eq!(metrics.registered_machines, 1);
+        assert_eq!(
+            (
+                metrics.period,
+                metrics.active_machines,
+                metrics.previous_active_machines
+            ),
+            (3, 0, 0)
+        );
+        assert_eq!(PeaqMor::mor_metrics_of().period, 1);
+    });
+}
+
+fn def_staking_config(curve: StakeCurve) -> StakingConfig<BalanceOf<Test>, u64> {
+    StakingConfig {
+        curve,
+        full_stake: 1_000_000_000_000_000_000u128,
+        max_bonus: Perbill::from_percent(50),
+        unbonding_period: 10,
+    }
+}
+
+fn bonded(owner: Public) -> BalanceOf<Test> {
+    Balances::balance_on_hold(&HoldReason::MachineBond, &owner)
+}
+
+#[test]
+fn stake_curve_test() {
+    let quarter = Perbill::from_percent(25);
+    assert_eq!(StakeCurve::Linear.apply(quarter), quarter);
+    assert_eq!(StakeCurve::Sqrt.apply(quarter), Perbill::from_percent(50));
+    assert_eq!(StakeCurve::Sqrt.apply(Perbill::one()), Perbill::one());
+
+    // Stakes above the full stake do not earn more.
+    let config = def_staking_config(StakeCurve::Linear);
+    assert_eq!(config.bonus(1_000, 250_000_000_000_000_000), 125);
+    assert_eq!(config.bonus(1_000, 2_000_000_000_000_000_000), 500);
+    assert_eq!(StakingConfig::<u128, u64>::default().bonus(1_000, 1_000), 0);
+
+    // The base reward leaves room for the maximum bonus within the period reward.
+    let base = config.base_reward(1_500);
+    assert_eq!(base, 1_000);
+    assert_eq!(base + config.bonus(base, 1_000_000_000_000_000_000), 1_500);
+    assert_eq!(
+        StakingConfig::<u128, u64>::default().base_reward(1_500),
+        1_500
+    );
+}
+
+#[test]
+fn staking_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let reward = 1_000_000_000_000_000_000u128;
+        let stake = 250_000_000_000_000_000u128;
+
+        // Try to set an inconsistent staking configuration, or as a regular user.
+        // Expect errors MorConfigIsNotConsistent and BadOrigin.
+        let mut config = def_staking_config(StakeCurve::Sqrt);
+        config.full_stake = 0;
         assert_noop!(
-            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
+            PeaqMor::set_staking_config(RuntimeOrigin::root(), config),
             Error::<Test>::MorConfigIsNotConsistent
         );
+        assert_noop!(
+            PeaqMor::set_staking_config(
+                RuntimeOrigin::signed(owner),
+                def_staking_config(StakeCurve::Sqrt)
+            ),
+            BadOrigin
+        );
+        assert_ok!(PeaqMor::set_staking_config(
+            RuntimeOrigin::root(),
+            def_staking_config(StakeCurve::Sqrt)
+        ));
 
-        // Try to set invalid configuration (minimum of fees is way too low).
-        // Expect error MorConfigIsNotConsistent.
-        let config = def_config(b_too_low, b_med, b_med, 0);
+        // Try to bond behind a machine, which is not registered, or not owned by the sender.
+        // Expect errors MachineNotRegistered and DidAuthorizationFailed.
+        register_machine_did(owner, machine);
         assert_noop!(
-            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
-            Error::<Test>::MorConfigIsNotConsistent
+            PeaqMor::bond(RuntimeOrigin::signed(owner), machine, stake),
+            Error::<Test>::MachineNotRegistered
         );
-        let config = def_config(b_low, b_too_low, b_med, 0);
+        get_registration_reward_mor(owner, machine);
         assert_noop!(
-            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
-            Error::<Test>::MorConfigIsNotConsistent
+            PeaqMor::bond(RuntimeOrigin::signed(muser), machine, stake),
+            Error::<Test>::DidAuthorizationFailed
         );
 
-        // Set valid configuration.
+        // Bond a quarter of the full stake, which earns half of the maximum bonus on top of
+        // the base reward, which is two thirds of the period reward.
         // Expect no error.
-        let config = def_config(b_low, b_med, b_max, 50);
-        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+        assert_ok!(PeaqMor::bond(RuntimeOrigin::signed(owner), machine, stake));
+        System::assert_last_event(
+            crate::Event::<Test>::Bonded {
+                machine,
+                amount: stake,
+            }
+            .into(),
+        );
+        assert_eq!(bonded(owner), stake);
+        PeaqMor::log_block_rewards(reward);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        let base = def_staking_config(StakeCurve::Sqrt).base_reward(reward);
+        System::assert_last_event(
+            crate::Event::<Test>::OnlineRewardPaid {
+                owner,
+                machine,
+                amount: base + base / 4,
+                period: PeaqMor::current_period(),
+            }
+            .into(),
+        );
 
-        // Set another valid configuration with more track_n_block_rewards.
+        // Try to unbond more than has been bonded.
+        // Expect error InsufficientBond.
+        assert_noop!(
+            PeaqMor::unbond(RuntimeOrigin::signed(owner), machine, stake + 1),
+            Error::<Test>::InsufficientBond
+        );
+        assert_noop!(
+            PeaqMor::unbond(RuntimeOrigin::signed(muser), machine, stake),
+            Error::<Test>::MachineNotBonded
+        );
+
+        // Unbond everything, the machine earns no bonus anymore, but tokens stay held.
         // Expect no error.
-        let config = def_config(b_low, b_med, b_max, 100);
-        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+        assert_ok!(PeaqMor::unbond(
+            RuntimeOrigin::signed(owner),
+            machine,
+            stake
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::Unbonded {
+                machine,
+                amount: stake,
+                unlock_at: 11,
+            }
+            .into(),
+        );
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::OnlineRewardPaid {
+                owner,
+                machine,
+                amount: base,
+                period: PeaqMor::current_period(),
+            }
+            .into(),
+        );
+        assert_eq!(bonded(owner), stake);
 
-        // Set another valid configuration with less track_n_block_rewards.
+        // Try to withdraw before the end of the unbonding period.
+        // Expect error BondIsUnbonding.
+        assert_noop!(
+            PeaqMor::withdraw_unbonded(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::BondIsUnbonding
+        );
+
+        // Withdraw after the unbonding period.
         // Expect no error.
-        let config = def_config(b_low, b_med, b_max, 25);
-        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+        System::set_block_number(11);
+        assert_ok!(PeaqMor::withdraw_unbonded(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::BondWithdrawn {
+                machine,
+                amount: stake,
+            }
+            .into(),
+        );
+        assert_eq!(bonded(owner), 0);
+        assert_eq!(PeaqMor::machine_bond_of(machine_hash(machine)), None);
     });
 }
 
 #[test]
-fn fetch_pot_balance_test() {
+fn slash_bond_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let stake = 250_000_000_000_000_000u128;
+
+        // Nothing to slash without a bond.
+        assert_eq!(PeaqMor::slash_bond(&machine, stake), 0);
+
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_ok!(PeaqMor::bond(RuntimeOrigin::signed(owner), machine, stake));
+        assert_ok!(PeaqMor::unbond(
+            RuntimeOrigin::signed(owner),
+            machine,
+            stake / 2
+        ));
+
+        // Slash the active tokens first, then the unbonding ones into the pot.
+        let pot_balance = Balances::free_balance(pot);
+        assert_eq!(PeaqMor::slash_bond(&machine, stake * 3 / 4), stake * 3 / 4);
+        System::assert_last_event(
+            crate::Event::<Test>::BondSlashed {
+                machine,
+                amount: stake * 3 / 4,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(pot), pot_balance + stake * 3 / 4);
+        let bond = PeaqMor::machine_bond_of(machine_hash(machine)).unwrap();
+        assert_eq!((bond.active, bond.unbonding), (0, stake / 4));
+        assert_eq!(bonded(owner), stake / 4);
+
+        // Slashing more than is bonded removes the bond.
+        assert_eq!(PeaqMor::slash_bond(&machine, stake), stake / 4);
+        assert_eq!(PeaqMor::machine_bond_of(machine_hash(machine)), None);
+        assert_eq!(bonded(owner), 0);
+    });
+}
+
+fn report_misbehaviour_mor(reporter: Public, machine: Public) {
+    assert_ok!(PeaqMor::report_misbehaviour(
+        RuntimeOrigin::signed(reporter),
+        machine,
+        b"spoofed heartbeats".to_vec().try_into().unwrap()
+    ));
+}
+
+#[test]
+fn misbehaviour_test() {
     new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
         let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let stake = 250_000_000_000_000_000u128;
+        let evidence: Evidence = b"spoofed heartbeats".to_vec().try_into().unwrap();
 
-        // Try to fetch configuration details as regular user.
-        // Expect error BadRuntimeOrigin.
+        // Try to report a machine, which is not registered in Peaq-MOR.
+        // Expect error MachineNotRegistered.
         assert_noop!(
-            PeaqMor::fetch_pot_balance(RuntimeOrigin::signed(muser)),
+            PeaqMor::report_misbehaviour(RuntimeOrigin::signed(muser), machine, evidence.clone()),
+            Error::<Test>::MachineNotRegistered
+        );
+
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_ok!(PeaqMor::bond(RuntimeOrigin::signed(owner), machine, stake));
+
+        // Report the machine, but only once until the report has been adjudicated.
+        // Expect error ReportAlreadyPending.
+        report_misbehaviour_mor(muser, machine);
+        System::assert_last_event(
+            crate::Event::<Test>::MisbehaviourReported {
+                machine,
+                reporter: muser,
+            }
+            .into(),
+        );
+        assert_noop!(
+            PeaqMor::report_misbehaviour(RuntimeOrigin::signed(muser), machine, evidence),
+            Error::<Test>::ReportAlreadyPending
+        );
+
+        // Try to adjudicate as a regular user, or a machine without a report.
+        // Expect errors BadOrigin and ReportNotFound.
+        assert_noop!(
+            PeaqMor::adjudicate_misbehaviour(RuntimeOrigin::signed(owner), machine, true),
             BadOrigin
         );
+        assert_noop!(
+            PeaqMor::adjudicate_misbehaviour(RuntimeOrigin::root(), muser, true),
+            Error::<Test>::ReportNotFound
+        );
 
-        // Try to fetch current pot-balance of the pallet.
+        // Dismiss the report, nothing will be slashed.
         // Expect no error.
-        assert_ok!(PeaqMor::fetch_pot_balance(RuntimeOrigin::root()));
+        assert_ok!(PeaqMor::adjudicate_misbehaviour(
+            RuntimeOrigin::root(),
+            machine,
+            false
+        ));
+        System::assert_last_event(crate::Event::<Test>::MisbehaviourDismissed { machine }.into());
+        assert_eq!(bonded(owner), stake);
+        assert!(PeaqMor::offences_of(machine_hash(machine)).is_empty());
+
+        // Confirm the next report, half of the bond will be slashed and the rewards will be
+        // frozen for two periods of 10 blocks.
+        // Expect no error.
+        report_misbehaviour_mor(muser, machine);
+        assert_ok!(PeaqMor::adjudicate_misbehaviour(
+            RuntimeOrigin::root(),
+            machine,
+            true
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::MisbehaviourConfirmed {
+                machine,
+                slashed: stake / 2,
+                frozen_until: 21,
+            }
+            .into(),
+        );
+        assert_eq!(bonded(owner), stake / 2);
+        assert_eq!(PeaqMor::offences_of(machine_hash(machine)).len(), 1);
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineRewardsFrozen
+        );
+        System::set_block_number(21);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+
+        // The history keeps the most recent offences only.
+        for block in 22..25 {
+            System::set_block_number(block);
+            report_misbehaviour_mor(muser, machine);
+            assert_ok!(PeaqMor::adjudicate_misbehaviour(
+                RuntimeOrigin::root(),
+                machine,
+                true
+            ));
+        }
+        let history = PeaqMor::offences_of(machine_hash(machine));
+        assert_eq!(
+            history.iter().map(|o| o.reported_at).collect::<Vec<_>>(),
+            vec![22, 23, 24]
+        );
     });
 }
 
+fn attestations(machines: &[(Public, bool)]) -> BoundedVec<(Public, bool), ConstU32<16>> {
+    machines.to_vec().try_into().unwrap()
+}
+
 #[test]
-fn log_block_rewards_test() {
+fn submit_liveness_test() {
     new_test_ext().execute_with(|| {
-        let balance = BalanceOf::<Test>::from(100_000_000_000_000_000u128);
-        // Try to log new block-reward in default genesis configuration.
-        // Expect no error.
-        PeaqMor::log_block_rewards(balance);
-    });
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+
+        // Attest a machine, which is not registered in Peaq-MOR.
+        // Expect the attestation to be skipped.
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(muser),
+            attestations(&[(machine, false)])
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::LivenessAttested {
+                reporter: muser,
+                count: 0,
+            }
+            .into(),
+        );
+        assert_eq!(PeaqMor::liveness_of(machine_hash(machine)), None);
+
+        // Attest the registered machine offline, it will not be rewarded for one period of
+        // 10 blocks.
+        // Expect error MachineOffline.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(muser),
+            attestations(&[(machine, false)])
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::LivenessAttested {
+                reporter: muser,
+                count: 1,
+            }
+            .into(),
+        );
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineOffline
+        );
+        System::set_block_number(11);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+
+        // Attest the machine online again.
+        // Expect no error.
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(muser),
+            attestations(&[(machine, false)])
+        ));
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(muser),
+            attestations(&[(machine, true)])
+        ));
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+    });
+}
+
+#[test]
+fn offline_until_period_end_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+
+        // Attest the machine offline in the last block of the first period of 10 blocks.
+        // Expect error MachineOffline.
+        System::set_block_number(9);
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(muser),
+            attestations(&[(machine, false)])
+        ));
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineOffline
+        );
+
+        // The attestation only covers the period, in which it has been made.
+        // Expect no error in the first block of the next period.
+        System::set_block_number(10);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+    });
+}
+
+#[test]
+fn liveness_threshold_test() {
+    new_test_ext().execute_with(|| {
+        LivenessThreshold::set(2);
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let reporters = [
+            account_key(U_ACCT),
+            account_key("Rep002"),
+            account_key("Rep003"),
+        ];
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+
+        // One reporter attests the machine offline, also twice.
+        // Expect the liveness not to change.
+        for _ in 0..2 {
+            assert_ok!(PeaqMor::submit_liveness(
+                RuntimeOrigin::signed(reporters[0]),
+                attestations(&[(machine, false)])
+            ));
+        }
+        assert_eq!(PeaqMor::liveness_tally_of(machine_hash(machine)).offline, 1);
+        assert_eq!(PeaqMor::liveness_of(machine_hash(machine)), None);
+
+        // A second reporter agrees.
+        // Expect error MachineOffline.
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(reporters[1]),
+            attestations(&[(machine, false)])
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::LivenessAgreed {
+                machine,
+                online: false,
+                period: 0,
+            }
+            .into(),
+        );
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineOffline
+        );
+
+        // The third reporter disagrees, then the first one changes its attestation.
+        // Expect the machine to be online, once two reporters and the majority agree.
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(reporters[2]),
+            attestations(&[(machine, true)])
+        ));
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(reporters[0]),
+            attestations(&[(machine, true)])
+        ));
+        let tally = PeaqMor::liveness_tally_of(machine_hash(machine));
+        assert_eq!((tally.online, tally.offline), (2, 1));
+        assert!(PeaqMor::liveness_of(machine_hash(machine)).unwrap().online);
+
+        // Attestations of the last period do not count in the next one.
+        // Expect the liveness not to change until two reporters agree again.
+        System::set_block_number(10);
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(reporters[1]),
+            attestations(&[(machine, false)])
+        ));
+        let tally = PeaqMor::liveness_tally_of(machine_hash(machine));
+        assert_eq!((tally.period, tally.online, tally.offline), (1, 0, 1));
+        assert!(PeaqMor::liveness_of(machine_hash(machine)).unwrap().online);
+
+        // Remove the machine.
+        // Expect its attestations to be removed too.
+        PeaqMor::remove_machine(machine_hash(machine));
+        assert_eq!(PeaqMor::liveness_tally_of(machine_hash(machine)).offline, 0);
+        assert_eq!(
+            PeaqMor::liveness_attestation_of(machine_hash(machine), reporters[1]),
+            None
+        );
+        LivenessThreshold::set(1);
+    });
+}
+
+fn signed_heartbeat(pair: &sr25519::Pair, period: u32) -> (Heartbeat<Public>, sr25519::Signature) {
+    let heartbeat = Heartbeat {
+        machine: pair.public(),
+        period,
+        nonce: 1,
+    };
+    let signature = pair.sign(&PeaqMor::heartbeat_payload(&heartbeat));
+    (heartbeat, signature)
+}
+
+fn validate_heartbeat(
+    heartbeat: &Heartbeat<Public>,
+    signature: &sr25519::Signature,
+) -> TransactionValidity {
+    let call = crate::Call::submit_heartbeat {
+        heartbeat: heartbeat.clone(),
+        _signature: signature.clone(),
+    };
+    <PeaqMor as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &call)
+}
+
+#[test]
+fn submit_heartbeat_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let pair = sr25519::Pair::from_seed(&[7u8; 32]);
+        let machine = pair.public();
+        let (heartbeat, signature) = signed_heartbeat(&pair, 0);
+
+        // Try to validate a heartbeat of a machine, which is not registered in Peaq-MOR.
+        // Expect the heartbeat to be invalid.
+        assert_eq!(
+            validate_heartbeat(&heartbeat, &signature),
+            InvalidTransaction::BadSigner.into()
+        );
+
+        // Try to validate heartbeats, which have been signed by another key or for a
+        // future period.
+        // Expect the heartbeats to be invalid.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        let other = sr25519::Pair::from_seed(&[8u8; 32]);
+        assert_eq!(
+            validate_heartbeat(
+                &heartbeat,
+                &other.sign(&PeaqMor::heartbeat_payload(&heartbeat))
+            ),
+            InvalidTransaction::BadProof.into()
+        );
+
+        // Try to validate heartbeats, whose signature does not cover the prefix or the
+        // genesis hash of this chain, e.g. replayed from another chain.
+        // Expect the heartbeats to be invalid.
+        assert_eq!(
+            validate_heartbeat(&heartbeat, &pair.sign(&heartbeat.encode())),
+            InvalidTransaction::BadProof.into()
+        );
+        let foreign = heartbeat.signing_payload(&sp_core::H256::repeat_byte(1));
+        assert_eq!(
+            validate_heartbeat(&heartbeat, &pair.sign(&foreign)),
+            InvalidTransaction::BadProof.into()
+        );
+        let (future, future_signature) = signed_heartbeat(&pair, 1);
+        assert_eq!(
+            validate_heartbeat(&future, &future_signature),
+            InvalidTransaction::Future.into()
+        );
+
+        // Validate the heartbeat, it lives until the end of the reward period of 10 blocks.
+        // Expect no error.
+        let valid = validate_heartbeat(&heartbeat, &signature).unwrap();
+        assert_eq!(valid.priority, u64::MAX / 2);
+        assert_eq!(valid.longevity, 9);
+
+        // Submit the heartbeat of a machine, which has been attested offline, but only once
+        // per period. The heartbeat proves the machine's liveness for the period.
+        // Expect errors BadOrigin and HeartbeatAlreadyReceived.
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(account_key(U_ACCT)),
+            attestations(&[(machine, false)])
+        ));
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineOffline
+        );
+        assert_noop!(
+            PeaqMor::submit_heartbeat(
+                RuntimeOrigin::signed(machine),
+                heartbeat.clone(),
+                signature.clone()
+            ),
+            BadOrigin
+        );
+        assert_ok!(PeaqMor::submit_heartbeat(
+            RuntimeOrigin::none(),
+            heartbeat.clone(),
+            signature.clone()
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::HeartbeatReceived { machine, period: 0 }.into(),
+        );
+        assert_eq!(PeaqMor::last_heartbeat_of(machine_hash(machine)), Some(0));
+        assert!(!PeaqMor::liveness_of(machine_hash(machine)).unwrap().online);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        assert_eq!(
+            validate_heartbeat(&heartbeat, &signature),
+            InvalidTransaction::Stale.into()
+        );
+        assert_noop!(
+            PeaqMor::submit_heartbeat(RuntimeOrigin::none(), heartbeat.clone(), signature.clone()),
+            Error::<Test>::HeartbeatAlreadyReceived
+        );
+
+        // Submit the heartbeat of the last period in the next one.
+        // Expect error HeartbeatOutdated.
+        System::set_block_number(10);
+        assert_eq!(PeaqMor::current_period(), 1);
+        assert_eq!(
+            validate_heartbeat(&heartbeat, &signature),
+            InvalidTransaction::Stale.into()
+        );
+        assert_noop!(
+            PeaqMor::submit_heartbeat(RuntimeOrigin::none(), heartbeat, signature),
+            Error::<Test>::HeartbeatOutdated
+        );
+
+        // Attest the machine offline in the next period, the heartbeat of the last period
+        // does not prove its liveness anymore, until it sends a new one.
+        // Expect error MachineOffline, then no error.
+        assert_ok!(PeaqMor::submit_liveness(
+            RuntimeOrigin::signed(account_key(U_ACCT)),
+            attestations(&[(machine, false)])
+        ));
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineOffline
+        );
+        assert_ok!(PeaqMor::submit_heartbeat(
+            RuntimeOrigin::none(),
+            future,
+            future_signature
+        ));
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+    });
+}
+
+#[test]
+fn offchain_worker_test() {
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let keystore = MemoryKeystore::new();
+    let reporter = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+
+    let mut test_ext = new_test_ext();
+    test_ext.register_extension(OffchainDbExt::new(offchain.clone()));
+    test_ext.register_extension(OffchainWorkerExt::new(offchain));
+    test_ext.register_extension(TransactionPoolExt::new(pool));
+    test_ext.register_extension(KeystoreExt::new(keystore));
+
+    test_ext.execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let other = account_key("RPi002");
+
+        // Publish the endpoint of one machine, the other one has no metadata.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        register_machine_did(owner, other);
+        get_registration_reward_mor(owner, other);
+        assert_ok!(PeaqMor::set_machine_metadata(
+            RuntimeOrigin::signed(owner),
+            machine,
+            Some(def_metadata(M_GEOHASH, M_ATTR))
+        ));
+        let unreachable = account_key("RPi003");
+        register_machine_did(owner, unreachable);
+        get_registration_reward_mor(owner, unreachable);
+        let mut metadata = def_metadata(M_GEOHASH, M_ATTR);
+        metadata.endpoint = b"\xff".to_vec().try_into().unwrap();
+        assert_ok!(PeaqMor::set_machine_metadata(
+            RuntimeOrigin::signed(owner),
+            unreachable,
+            Some(metadata)
+        ));
+
+        // Run the offchain worker within a reward period.
+        // Expect no request and no transaction.
+        PeaqMor::offchain_worker(11);
+        assert!(pool_state.read().transactions.is_empty());
+
+        // Run the offchain worker at the start of a reward period, the valid endpoint
+        // responds, no request can be sent to the invalid one.
+        // Expect one signed transaction, which attests one machine online, the other offline.
+        offchain_state.write().expect_request(PendingRequest {
+            method: "GET".into(),
+            uri: String::from_utf8(M_ENDPOINT.to_vec()).unwrap(),
+            response: Some(b"ok".to_vec()),
+            sent: true,
+            ..Default::default()
+        });
+        PeaqMor::offchain_worker(20);
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert!(tx.signature.is_some());
+        let mut attested = match tx.call.clone() {
+            RuntimeCall::PeaqMor(crate::Call::submit_liveness { attestations }) => {
+                attestations.into_inner()
+            }
+            _ => panic!("unexpected call"),
+        };
+        attested.sort();
+        let mut expected = vec![(machine, true), (unreachable, false)];
+        expected.sort();
+        assert_eq!(attested, expected);
+
+        // Submit the attestation like the transaction pool would do.
+        // Expect no error.
+        assert_ok!(tx.call.dispatch(RuntimeOrigin::signed(reporter)));
+        assert!(PeaqMor::liveness_of(machine_hash(machine)).unwrap().online);
+    });
+}
+
+fn check_machine_fee(
+    who: Public,
+    call: &RuntimeCall,
+) -> Option<<ChargeTransactionPayment<Test> as SignedExtension>::Pre> {
+    let info = DispatchInfo {
+        weight: Weight::from_parts(1_000, 0),
+        ..Default::default()
+    };
+    CheckMorMachineFee::<Test, _>::new(ChargeTransactionPayment::<Test>::from(0))
+        .pre_dispatch(&who, call, &info, 10)
+        .unwrap()
+}
+
+#[test]
+fn check_mor_machine_fee_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let usage = RuntimeCall::PeaqMor(crate::Call::pay_machine_usage {
+            machine,
+            amount: 500_000_000_000_000_000,
+        });
+        let donation = RuntimeCall::PeaqMor(crate::Call::donate_to_pot { amount: 1 });
+
+        // A machine, which is not registered, calls a whitelisted call.
+        // Expect the fees to be charged.
+        let balance = Balances::free_balance(machine);
+        assert!(check_machine_fee(machine, &usage).is_some());
+        assert!(Balances::free_balance(machine) < balance);
+
+        // The registered machine calls a whitelisted call within its quota of two
+        // transactions per period.
+        // Expect the fees to be waived.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        let balance = Balances::free_balance(machine);
+        for used in 1..=2 {
+            assert!(check_machine_fee(machine, &usage).is_none());
+            assert_eq!(
+                PeaqMor::feeless_calls_used_of(machine_hash(machine)),
+                (0, used)
+            );
+        }
+        assert_eq!(Balances::free_balance(machine), balance);
+
+        // The machine exceeds its quota, or calls a call, which is not whitelisted.
+        // Expect the fees to be charged.
+        assert!(check_machine_fee(machine, &usage).is_some());
+        assert!(check_machine_fee(machine, &donation).is_some());
+        assert!(Balances::free_balance(machine) < balance);
+
+        // The quota will be renewed in the next period.
+        // Expect the fees to be waived.
+        System::set_block_number(10);
+        let balance = Balances::free_balance(machine);
+        assert!(check_machine_fee(machine, &usage).is_none());
+        assert_eq!(
+            PeaqMor::feeless_calls_used_of(machine_hash(machine)),
+            (1, 1)
+        );
+        assert_eq!(Balances::free_balance(machine), balance);
+    });
+}
+
+#[test]
+fn pay_machine_usage_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let amount = BalanceOf::<Test>::from(500_000_000_000_000_000u128);
+        let amount_oor = BalanceOf::<Test>::from(5_000_000_000_000_000_000u128);
+
+        // Try to pay for machine usage.
+        // Expect no error.
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            amount
+        ));
+
+        // Try to pay out of range.
+        // Expect error MachinePaymentOutOfRange.
+        assert_noop!(
+            PeaqMor::pay_machine_usage(RuntimeOrigin::signed(muser), machine, amount_oor),
+            Error::<Test>::MachinePaymentOutOfRange
+        );
+    });
+}
+
+fn def_asset_config(fee_min: u128, fee_max: u128) -> AssetConfig<BalanceOf<Test>> {
+    AssetConfig {
+        machine_usage_fee_min: BalanceOf::<Test>::from(fee_min),
+        machine_usage_fee_max: BalanceOf::<Test>::from(fee_max),
+        reward_rate: FixedU128::saturating_from_integer(2u32),
+    }
+}
+
+fn def_tariff(flat_fee: u128, unit_price: u128) -> MachineTariff<BalanceOf<Test>, AssetIdOf<Test>> {
+    MachineTariff {
+        flat_fee: BalanceOf::<Test>::from(flat_fee),
+        unit_price: BalanceOf::<Test>::from(unit_price),
+        currency: TariffCurrency::Native,
+    }
+}
+
+#[test]
+fn set_machine_tariff_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let tariff = def_tariff(200_000_000_000_000_000, 100_000_000_000_000_000);
+
+        // Try to set a tariff for a machine, which is not registered in Peaq-MOR.
+        // Expect error MachineNotRegistered.
+        register_machine_did(owner, machine);
+        assert_noop!(
+            PeaqMor::set_machine_tariff(
+                RuntimeOrigin::signed(owner),
+                machine,
+                Some(tariff.clone())
+            ),
+            Error::<Test>::MachineNotRegistered
+        );
+        get_registration_reward_mor(owner, machine);
+
+        // Try to set a tariff as someone, who does not own the machine.
+        // Expect error DidAuthorizationFailed.
+        assert_noop!(
+            PeaqMor::set_machine_tariff(
+                RuntimeOrigin::signed(muser),
+                machine,
+                Some(tariff.clone())
+            ),
+            Error::<Test>::DidAuthorizationFailed
+        );
+
+        // Try to set a tariff, which exceeds the configured maximum usage fee.
+        // Expect error MachineTariffOutOfRange.
+        assert_noop!(
+            PeaqMor::set_machine_tariff(
+                RuntimeOrigin::signed(owner),
+                machine,
+                Some(def_tariff(3_000_000_000_000_000_000, 1))
+            ),
+            Error::<Test>::MachineTariffOutOfRange
+        );
+
+        // Set a valid tariff.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_machine_tariff(
+            RuntimeOrigin::signed(owner),
+            machine,
+            Some(tariff.clone())
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::MachineTariffChanged {
+                machine,
+                tariff: Some(tariff.clone()),
+            }
+            .into(),
+        );
+
+        // Try to pay an amount, which is within the range but not matching the tariff.
+        // Expect error MachinePaymentDoesNotMatchTariff.
+        assert_noop!(
+            PeaqMor::pay_machine_usage(
+                RuntimeOrigin::signed(muser),
+                machine,
+                BalanceOf::<Test>::from(250_000_000_000_000_000u128)
+            ),
+            Error::<Test>::MachinePaymentDoesNotMatchTariff
+        );
+
+        // Pay flat fee plus three units.
+        // Expect no error.
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            BalanceOf::<Test>::from(500_000_000_000_000_000u128)
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::UsagePaid {
+                payer: muser,
+                machine,
+                amount: BalanceOf::<Test>::from(500_000_000_000_000_000u128),
+                fee: tariff.flat_fee,
+            }
+            .into(),
+        );
+
+        // Remove the tariff again, now any amount within the range will be accepted.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_machine_tariff(
+            RuntimeOrigin::signed(owner),
+            machine,
+            None
+        ));
+        assert_eq!(PeaqMor::machine_tariff_of(machine_hash(machine)), None);
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            BalanceOf::<Test>::from(250_000_000_000_000_000u128)
+        ));
+    });
+}
+
+fn def_metadata(geohash: &[u8], did_attribute: &[u8]) -> MachineMetadata {
+    MachineMetadata {
+        machine_type: M_VAL.to_vec().try_into().unwrap(),
+        geohash: geohash.to_vec().try_into().unwrap(),
+        capacity: 22,
+        firmware: M_FIRMWARE.to_vec().try_into().unwrap(),
+        did_attribute: did_attribute.to_vec().try_into().unwrap(),
+        endpoint: M_ENDPOINT.to_vec().try_into().unwrap(),
+    }
+}
+
+#[test]
+fn set_machine_metadata_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let metadata = def_metadata(M_GEOHASH, M_ATTR);
+
+        // Try to set metadata of a machine, which is not registered in Peaq-MOR.
+        // Expect error MachineNotRegistered.
+        register_machine_did(owner, machine);
+        assert_noop!(
+            PeaqMor::set_machine_metadata(
+                RuntimeOrigin::signed(owner),
+                machine,
+                Some(metadata.clone())
+            ),
+            Error::<Test>::MachineNotRegistered
+        );
+
+        get_registration_reward_mor(owner, machine);
+
+        // Try to set metadata as another user than the machine's owner.
+        // Expect error DidAuthorizationFailed.
+        assert_noop!(
+            PeaqMor::set_machine_metadata(
+                RuntimeOrigin::signed(muser),
+                machine,
+                Some(metadata.clone())
+            ),
+            Error::<Test>::DidAuthorizationFailed
+        );
+
+        // Try to set metadata with an invalid geohash ("a" is not part of its alphabet).
+        // Expect error MachineMetadataIsNotConsistent.
+        assert_noop!(
+            PeaqMor::set_machine_metadata(
+                RuntimeOrigin::signed(owner),
+                machine,
+                Some(def_metadata(b"u33dca", M_ATTR))
+            ),
+            Error::<Test>::MachineMetadataIsNotConsistent
+        );
+
+        // Try to set metadata, which refers to a missing attribute in Peaq-DID.
+        // Expect error DidAttributeNotFound.
+        assert_noop!(
+            PeaqMor::set_machine_metadata(
+                RuntimeOrigin::signed(owner),
+                machine,
+                Some(def_metadata(M_GEOHASH, b"Firmware"))
+            ),
+            Error::<Test>::DidAttributeNotFound
+        );
+
+        // Try to set metadata with an endpoint, which is no HTTP(S) URL.
+        // Expect error MachineMetadataIsNotConsistent.
+        let mut ftp = metadata.clone();
+        ftp.endpoint = b"ftp://rpi001.example.com".to_vec().try_into().unwrap();
+        assert_noop!(
+            PeaqMor::set_machine_metadata(RuntimeOrigin::signed(owner), machine, Some(ftp)),
+            Error::<Test>::MachineMetadataIsNotConsistent
+        );
+
+        // Set the metadata and read it like the runtime API does.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_machine_metadata(
+            RuntimeOrigin::signed(owner),
+            machine,
+            Some(metadata.clone())
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::MachineMetadataChanged {
+                machine,
+                metadata: Some(metadata.clone()),
+            }
+            .into(),
+        );
+        assert_eq!(PeaqMor::machine_metadata(&machine), Some(metadata));
+
+        // Remove the metadata.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_machine_metadata(
+            RuntimeOrigin::signed(owner),
+            machine,
+            None
+        ));
+        assert_eq!(PeaqMor::machine_metadata(&machine), None);
+    });
+}
+
+fn def_rules(rules: &[(&[u8], Option<&[u8]>)]) -> EligibilityRules {
+    let rules: Vec<EligibilityRule> = rules
+        .iter()
+        .map(|(name, value)| EligibilityRule {
+            name: name.to_vec().try_into().unwrap(),
+            value: value.map(|v| v.to_vec().try_into().unwrap()),
+        })
+        .collect();
+    rules.try_into().unwrap()
+}
+
+#[test]
+fn set_eligibility_rules_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let rules = def_rules(&[(M_ATTR, Some(M_VAL)), (b"Firmware", None)]);
+
+        // Try to set the rules as a regular user.
+        // Expect error BadOrigin.
+        assert_noop!(
+            PeaqMor::set_eligibility_rules(RuntimeOrigin::signed(owner), rules.clone()),
+            BadOrigin
+        );
+
+        // Try to set a rule without an attribute's name.
+        // Expect error MorConfigIsNotConsistent.
+        assert_noop!(
+            PeaqMor::set_eligibility_rules(RuntimeOrigin::root(), def_rules(&[(b"", None)])),
+            Error::<Test>::MorConfigIsNotConsistent
+        );
+
+        // Set the rules.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_eligibility_rules(
+            RuntimeOrigin::root(),
+            rules.clone()
+        ));
+        assert_eq!(PeaqMor::eligibility_rules(), rules);
+        System::assert_last_event(crate::Event::<Test>::EligibilityRulesChanged { rules }.into());
+
+        // Remove the rules again.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_eligibility_rules(
+            RuntimeOrigin::root(),
+            def_rules(&[])
+        ));
+        assert!(PeaqMor::eligibility_rules().is_empty());
+    });
+}
+
+#[test]
+fn eligibility_rules_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        register_machine_did(owner, machine);
+        assert_ok!(PeaqMor::set_eligibility_rules(
+            RuntimeOrigin::root(),
+            def_rules(&[(M_ATTR, Some(M_VAL)), (b"Firmware", None)])
+        ));
+
+        // Try to register a machine, which misses the required attribute "Firmware".
+        // Expect error MachineNotEligible.
+        assert_noop!(
+            PeaqMor::get_registration_reward(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineNotEligible
+        );
+
+        // Add the attribute, which is valid for 5 blocks only, and register the machine.
+        // Expect no error.
+        add_machine_attribute(owner, machine, b"Firmware", M_FIRMWARE, Some(5));
+        get_registration_reward_mor(owner, machine);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+
+        // Wait until the attribute has expired and try to get online rewards.
+        // Expect error MachineNotEligible.
+        System::set_block_number(System::block_number() + 5);
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineNotEligible
+        );
+
+        // Require another value of the generic attribute.
+        // Expect error MachineNotEligible, too.
+        assert_ok!(PeaqMor::set_eligibility_rules(
+            RuntimeOrigin::root(),
+            def_rules(&[(M_ATTR, Some(b"EVCharger"))])
+        ));
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MachineNotEligible
+        );
+
+        // Require the generic attribute with any value.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_eligibility_rules(
+            RuntimeOrigin::root(),
+            def_rules(&[(M_ATTR, None)])
+        ));
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+    });
+}
+
+fn set_stale_owner(owner: Public, machine: Public) {
+    MachineRegister::<Test>::insert(machine_hash(machine), owner.using_encoded(blake2_256));
+    MachineAccounts::<Test>::insert(machine_hash(machine), (machine, owner));
+}
+
+#[test]
+fn reap_stale_machine_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let reaper = account_key("Charlie");
+        let machine = account_key(M_ACCT);
+
+        // Try to reap a machine, which is not registered in Peaq-MOR.
+        // Expect error MachineNotRegistered.
+        assert_noop!(
+            PeaqMor::reap_stale_machine(RuntimeOrigin::signed(reaper), machine),
+            Error::<Test>::MachineNotRegistered
+        );
+
+        // Try to reap a registration, which is still backed by Peaq-DID.
+        // Expect error MachineNotStale.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_noop!(
+            PeaqMor::reap_stale_machine(RuntimeOrigin::signed(reaper), machine),
+            Error::<Test>::MachineNotStale
+        );
+
+        // The document has been moved from muser to owner in Peaq-DID, so the new owner
+        // reclaims the registration, without the tariff and metadata of muser.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_machine_tariff(
+            RuntimeOrigin::signed(owner),
+            machine,
+            Some(def_tariff(200_000_000_000_000_000, 100_000_000_000_000_000))
+        ));
+        assert_ok!(PeaqMor::set_machine_metadata(
+            RuntimeOrigin::signed(owner),
+            machine,
+            Some(def_metadata(M_GEOHASH, M_ATTR))
+        ));
+        set_stale_owner(muser, machine);
+        assert_noop!(
+            PeaqMor::get_online_rewards(RuntimeOrigin::signed(owner), machine),
+            Error::<Test>::MorAuthorizationFailed
+        );
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::MachineReassigned { machine, owner }.into(),
+        );
+        assert_eq!(
+            PeaqMor::machine_accounts_of(machine_hash(machine)),
+            Some((machine, owner))
+        );
+        assert_eq!(PeaqMor::machine_tariff_of(machine_hash(machine)), None);
+        assert_eq!(PeaqMor::machine_metadata(&machine), None);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+
+        // Someone else reaps the stale registration and gets the bounty.
+        // Expect no error.
+        set_stale_owner(muser, machine);
+        let balance = Balances::free_balance(reaper);
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(reaper),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::StaleMachineReaped {
+                machine,
+                reaper,
+                bounty: REAP_BOUNTY,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(reaper), balance + REAP_BOUNTY);
+        assert!(!MachineRegister::<Test>::contains_key(machine_hash(
+            machine
+        )));
+        assert_eq!(PeaqMor::machine_accounts_of(machine_hash(machine)), None);
+
+        // The new owner can register the machine again.
+        // Expect no error.
+        get_registration_reward_mor(owner, machine);
+
+        // The former owner reaps the own stale registration, but gets no bounty.
+        // Expect no error.
+        set_stale_owner(muser, machine);
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(muser),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::StaleMachineReaped {
+                machine,
+                reaper: muser,
+                bounty: 0,
+            }
+            .into(),
+        );
+
+        // Try to reap a registration without accounts, its former owner is unknown.
+        // Expect error MachineNotStale.
+        MachineRegister::<Test>::insert(machine_hash(machine), muser.using_encoded(blake2_256));
+        assert_noop!(
+            PeaqMor::reap_stale_machine(RuntimeOrigin::signed(reaper), machine),
+            Error::<Test>::MachineNotStale
+        );
+    });
+}
+
+#[test]
+fn reap_and_register_again_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let reaper = account_key("Charlie");
+        let machine = account_key(M_ACCT);
+
+        // Register a machine, which pays the registration reward.
+        // Expect no error.
+        register_machine_did(owner, machine);
+        let balance = Balances::free_balance(owner);
+        get_registration_reward_mor(owner, machine);
+        assert_eq!(Balances::free_balance(owner), balance + REG_FEE);
+        assert!(PeaqMor::machine_tombstone_of(machine_hash(machine)).registration_rewarded);
+
+        // The owner drops the machine's identity and another account reaps it.
+        // Expect the bounty to be paid.
+        Identity::set_owner(&machine, None);
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(reaper),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::StaleMachineReaped {
+                machine,
+                reaper,
+                bounty: REAP_BOUNTY,
+            }
+            .into(),
+        );
+
+        // The owner restores the identity and registers the machine again.
+        // Expect no second registration reward.
+        register_machine_did(owner, machine);
+        let balance = Balances::free_balance(owner);
+        get_registration_reward_mor(owner, machine);
+        System::assert_last_event(
+            crate::Event::<Test>::MachineRegistered {
+                owner,
+                machine,
+                reward: 0,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(owner), balance);
+
+        // The machine goes stale again and is reaped again.
+        // Expect no second bounty.
+        Identity::set_owner(&machine, None);
+        let balance = Balances::free_balance(reaper);
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(reaper),
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::StaleMachineReaped {
+                machine,
+                reaper,
+                bounty: 0,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(reaper), balance);
+    });
+}
+
+#[test]
+fn reap_bonded_machine_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let reaper = account_key("Charlie");
+        let machine = account_key(M_ACCT);
+        let stake = 250_000_000_000_000_000u128;
+        assert_ok!(PeaqMor::set_staking_config(
+            RuntimeOrigin::root(),
+            def_staking_config(StakeCurve::Linear)
+        ));
+
+        // The former owner has bonded tokens behind the machine, whose document has been
+        // moved to owner in Peaq-DID. The new owner reclaims the registration.
+        // Expect the bond to be released and no error.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        set_stale_owner(muser, machine);
+        assert_ok!(Balances::hold(&HoldReason::MachineBond, &muser, stake));
+        MachineBonds::<Test>::insert(
+            machine_hash(machine),
+            MachineBond {
+                owner: muser,
+                active: stake,
+                unbonding: 0,
+                unlock_at: 0,
+            },
+        );
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        assert_eq!(bonded(muser), 0);
+        assert_eq!(PeaqMor::machine_bond_of(machine_hash(machine)), None);
+
+        // The new owner bonds behind the re-assigned machine.
+        // Expect no error.
+        assert_ok!(PeaqMor::bond(RuntimeOrigin::signed(owner), machine, stake));
+        assert_eq!(bonded(owner), stake);
+
+        // Someone else reaps the registration, after the document has been moved again.
+        // Expect the bond to be released and no error.
+        set_stale_owner(muser, machine);
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(reaper),
+            machine
+        ));
+        assert_eq!(bonded(owner), 0);
+        assert_eq!(PeaqMor::machine_bond_of(machine_hash(machine)), None);
+    });
+}
+
+#[test]
+fn reap_reported_machine_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let reaper = account_key("Charlie");
+        let machine = account_key(M_ACCT);
+
+        // The machine has a confirmed offence, which froze its rewards, and a pending report.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        report_misbehaviour_mor(muser, machine);
+        assert_ok!(PeaqMor::adjudicate_misbehaviour(
+            RuntimeOrigin::root(),
+            machine,
+            true
+        ));
+        report_misbehaviour_mor(muser, machine);
+        assert!(PeaqMor::pending_report_of(machine_hash(machine)).is_some());
+        assert_eq!(PeaqMor::offences_of(machine_hash(machine)).len(), 1);
+        assert!(PeaqMor::rewards_frozen_until(machine_hash(machine)).is_some());
+
+        // Someone reaps the stale registration.
+        // Expect the report, the offences and the freeze to be removed too.
+        set_stale_owner(muser, machine);
+        assert_ok!(PeaqMor::reap_stale_machine(
+            RuntimeOrigin::signed(reaper),
+            machine
+        ));
+        assert_eq!(PeaqMor::pending_report_of(machine_hash(machine)), None);
+        assert!(PeaqMor::offences_of(machine_hash(machine)).is_empty());
+        assert_eq!(PeaqMor::rewards_frozen_until(machine_hash(machine)), None);
+    });
+}
+
+#[test]
+fn set_accepted_asset_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+
+        // Try to whitelist an asset as regular user.
+        // Expect error BadOrigin.
+        assert_noop!(
+            PeaqMor::set_accepted_asset(
+                RuntimeOrigin::signed(muser),
+                ASSET_ID,
+                Some(def_asset_config(100, 1_000_000))
+            ),
+            BadOrigin
+        );
+
+        // Try to whitelist an asset with a minimum fee below the asset's minimum balance.
+        // Expect error MorConfigIsNotConsistent.
+        assert_noop!(
+            PeaqMor::set_accepted_asset(
+                RuntimeOrigin::root(),
+                ASSET_ID,
+                Some(def_asset_config(ASSET_MIN_BALANCE - 1, 1_000_000))
+            ),
+            Error::<Test>::MorConfigIsNotConsistent
+        );
+
+        // Whitelist the asset and use it for online rewards.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_accepted_asset(
+            RuntimeOrigin::root(),
+            ASSET_ID,
+            Some(def_asset_config(100, 1_000_000))
+        ));
+        assert_ok!(PeaqMor::set_reward_asset(
+            RuntimeOrigin::root(),
+            Some(ASSET_ID)
+        ));
+
+        // Reset the asset's reward rate, online rewards fall back to native currency. An
+        // asset without reward rate cannot be used for online rewards.
+        // Expect error AssetWithoutRewardRate.
+        let mut config = def_asset_config(100, 1_000_000);
+        config.reward_rate = FixedU128::zero();
+        assert_ok!(PeaqMor::set_accepted_asset(
+            RuntimeOrigin::root(),
+            ASSET_ID,
+            Some(config)
+        ));
+        assert_eq!(PeaqMor::reward_asset_of(), None);
+        assert_noop!(
+            PeaqMor::set_reward_asset(RuntimeOrigin::root(), Some(ASSET_ID)),
+            Error::<Test>::AssetWithoutRewardRate
+        );
+
+        // Remove the asset from the whitelist, online rewards fall back to native currency.
+        // Expect no error.
+        assert_ok!(PeaqMor::set_accepted_asset(
+            RuntimeOrigin::root(),
+            ASSET_ID,
+            Some(def_asset_config(100, 1_000_000))
+        ));
+        assert_ok!(PeaqMor::set_reward_asset(
+            RuntimeOrigin::root(),
+            Some(ASSET_ID)
+        ));
+        assert_ok!(PeaqMor::set_accepted_asset(
+            RuntimeOrigin::root(),
+            ASSET_ID,
+            None
+        ));
+        assert_eq!(PeaqMor::accepted_asset_of(ASSET_ID), None);
+        assert_eq!(PeaqMor::reward_asset_of(), None);
+
+        // Try to use a not whitelisted asset for online rewards.
+        // Expect error AssetNotAccepted.
+        assert_noop!(
+            PeaqMor::set_reward_asset(RuntimeOrigin::root(), Some(ASSET_ID)),
+            Error::<Test>::AssetNotAccepted
+        );
+    });
+}
+
+#[test]
+fn pay_machine_usage_in_asset_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+
+        // Try to pay in an asset, which is not whitelisted.
+        // Expect error AssetNotAccepted.
+        assert_noop!(
+            PeaqMor::pay_machine_usage_in_asset(
+                RuntimeOrigin::signed(muser),
+                machine,
+                ASSET_ID,
+                500
+            ),
+            Error::<Test>::AssetNotAccepted
+        );
+
+        assert_ok!(PeaqMor::set_accepted_asset(
+            RuntimeOrigin::root(),
+            ASSET_ID,
+            Some(def_asset_config(100, 1_000_000))
+        ));
+
+        // Try to pay out of the asset's range.
+        // Expect error MachinePaymentOutOfRange.
+        assert_noop!(
+            PeaqMor::pay_machine_usage_in_asset(
+                RuntimeOrigin::signed(muser),
+                machine,
+                ASSET_ID,
+                2_000_000
+            ),
+            Error::<Test>::MachinePaymentOutOfRange
+        );
+
+        // Pay for machine usage in the asset. Nothing is minted, user pays the machine.
+        // Expect no error.
+        let issuance = Assets::total_supply(ASSET_ID);
+        let user_balance = Assets::balance(ASSET_ID, muser);
+        assert_ok!(PeaqMor::pay_machine_usage_in_asset(
+            RuntimeOrigin::signed(muser),
+            machine,
+            ASSET_ID,
+            500
+        ));
+        assert_eq!(Assets::balance(ASSET_ID, machine), 500);
+        assert_eq!(Assets::balance(ASSET_ID, muser), user_balance - 500);
+        assert_eq!(Assets::total_supply(ASSET_ID), issuance);
+
+        // Owner publishes a tariff in the asset, native payments do not match anymore.
+        // Expect error MachinePaymentDoesNotMatchTariff.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        let mut tariff = def_tariff(200, 100);
+        tariff.currency = TariffCurrency::Asset(ASSET_ID);
+        assert_ok!(PeaqMor::set_machine_tariff(
+            RuntimeOrigin::signed(owner),
+            machine,
+            Some(tariff)
+        ));
+        assert_noop!(
+            PeaqMor::pay_machine_usage(
+                RuntimeOrigin::signed(muser),
+                machine,
+                BalanceOf::<Test>::from(500_000_000_000_000_000u128)
+            ),
+            Error::<Test>::MachinePaymentDoesNotMatchTariff
+        );
+        assert_ok!(PeaqMor::pay_machine_usage_in_asset(
+            RuntimeOrigin::signed(muser),
+            machine,
+            ASSET_ID,
+            400
+        ));
+
+        // The payment counts by its native equivalent, at a reward rate of two.
+        let activity = PeaqMor::machine_stats(&machine).unwrap().activity;
+        assert_eq!((activity.usage_revenue, activity.usage_payments), (200, 1));
+        assert_eq!(PeaqMor::owner_stats(&owner).usage_revenue, 200);
+    });
+}
+
+#[test]
+fn get_online_rewards_in_asset_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        PeaqMor::log_block_rewards(BalanceOf::<Test>::from(1_000u128));
+
+        assert_ok!(PeaqMor::set_accepted_asset(
+            RuntimeOrigin::root(),
+            ASSET_ID,
+            Some(def_asset_config(100, 1_000_000))
+        ));
+        assert_ok!(PeaqMor::set_reward_asset(
+            RuntimeOrigin::root(),
+            Some(ASSET_ID)
+        ));
+
+        // Online rewards are now paid in the asset out of the pot, converted by the reward
+        // rate of 2. Statistics and metrics record the native equivalent.
+        // Expect no error.
+        let native_balance = Balances::free_balance(owner);
+        let paid_from_pot = PeaqMor::mor_metrics_of().paid_from_pot;
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        assert_eq!(Assets::balance(ASSET_ID, owner), 2_000);
+        assert_eq!(Balances::free_balance(owner), native_balance);
+        assert_eq!(
+            PeaqMor::machine_stats_of(machine_hash(machine))
+                .activity
+                .online_rewards,
+            1_000
+        );
+        assert_eq!(
+            PeaqMor::mor_metrics_of().paid_from_pot,
+            paid_from_pot + 1_000
+        );
+
+        // Keep all but 2_000 units of the asset as reserve, the threshold is at 4_000 units.
+        // Online rewards will be scaled down by available / threshold = 1/2.
+        // Expect no error.
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Assets::balance(ASSET_ID, pot);
+        MorConfigStorage::<Test>::mutate(|config| {
+            config.pot_reserve = (pot_balance - 2_000) / 2;
+            config.pot_low_threshold = 2_000;
+        });
+        System::set_block_number(10);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        assert_eq!(Assets::balance(ASSET_ID, owner), 3_000);
+        assert_eq!(
+            PeaqMor::machine_stats_of(machine_hash(machine))
+                .activity
+                .online_rewards,
+            1_500
+        );
+    });
+}
+
+#[test]
+fn set_configuration_test() {
+    new_test_ext().execute_with(|| {
+        let b_too_low = BalanceOf::<Test>::from(250u128);
+        let b_low = BalanceOf::<Test>::from(100_000_000_000_000u128);
+        let b_med = BalanceOf::<Test>::from(500_000_000_000_000u128);
+        let b_max = BalanceOf::<Test>::from(2_500_000_000_000_000u128);
+
+        // Try to set invalid configuration (range of balances).
+        // Expect error MorConfigIsNotConsistent.
+        let config = def_config(b_low, b_med, b_med, 50);
+        assert_noop!(
+            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
+            Error::<Test>::MorConfigIsNotConsistent
+        );
+
+        // Try to set invalid configuration (number of blocks).
+        // Expect error MorConfigIsNotConsistent.
+        let config = def_config(b_low, b_med, b_med, 0);
+        assert_noop!(
+            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
+            Error::<Test>::MorConfigIsNotConsistent
+        );
+
+        // Try to set invalid configuration (minimum of fees is way too low).
+        // Expect error MorConfigIsNotConsistent.
+        let config = def_config(b_too_low, b_med, b_med, 0);
+        assert_noop!(
+            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
+            Error::<Test>::MorConfigIsNotConsistent
+        );
+        let config = def_config(b_low, b_too_low, b_med, 0);
+        assert_noop!(
+            PeaqMor::set_configuration(RuntimeOrigin::root(), config),
+            Error::<Test>::MorConfigIsNotConsistent
+        );
+
+        // Set valid configuration.
+        // Expect no error.
+        let config = def_config(b_low, b_med, b_max, 50);
+        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+
+        // Set another valid configuration with more track_n_block_rewards.
+        // Expect no error.
+        let config = def_config(b_low, b_med, b_max, 100);
+        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+
+        // Set another valid configuration with less track_n_block_rewards.
+        // Expect no error.
+        let config = def_config(b_low, b_med, b_max, 25);
+        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+    });
+}
+
+#[test]
+fn fetch_pot_balance_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+
+        // Try to fetch configuration details as regular user.
+        // Expect error BadRuntimeOrigin.
+        assert_noop!(
+            PeaqMor::fetch_pot_balance(RuntimeOrigin::signed(muser)),
+            BadOrigin
+        );
+
+        // Try to fetch current pot-balance of the pallet.
+        // Expect no error.
+        assert_ok!(PeaqMor::fetch_pot_balance(RuntimeOrigin::root()));
+    });
+}
+
+#[test]
+fn mint_to_account_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let fresh = account_key("RPi002");
+        let amount = BalanceOf::<Test>::from(500_000_000_000_000_000u128);
+
+        // Registration rewards increase the total issuance by the registration reward.
+        // Expect no error.
+        let issuance = Balances::total_issuance();
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_eq!(Balances::total_issuance(), issuance + REG_FEE);
+        System::assert_has_event(
+            crate::Event::<Test>::MintedTokens {
+                account: owner,
+                amount: REG_FEE,
+                remaining_in_period: MINT_CAP - REG_FEE,
+                remaining_budget: EMISSION_BUDGET - REG_FEE,
+            }
+            .into(),
+        );
+
+        // Machine usage payments increase the total issuance by the payed amount.
+        // Expect no error.
+        let issuance = Balances::total_issuance();
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            fresh,
+            amount
+        ));
+        assert_eq!(Balances::total_issuance(), issuance + amount);
+        assert_eq!(Balances::free_balance(fresh), amount);
+
+        // Try to mint dust to an account, which does not exist yet.
+        // Expect error TokensCouldNotBeTransfered, total issuance stays the same.
+        let issuance = Balances::total_issuance();
+        assert_noop!(
+            PeaqMor::mint_to_account(
+                &account_key("RPi003"),
+                ExistentialDeposit::get() - 1,
+                MintReason::Usage
+            ),
+            Error::<Test>::TokensCouldNotBeTransfered
+        );
+        assert_eq!(Balances::total_issuance(), issuance);
+    });
+}
+
+#[test]
+fn mint_caps_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+        let fee = BalanceOf::<Test>::from(1_000_000_000_000_000_000u128);
+
+        // Allow two maximum usage payments per period of 10 blocks and five in total.
+        let mut config = def_config(
+            BalanceOf::<Test>::from(REG_FEE),
+            BalanceOf::<Test>::from(REG_FEE),
+            fee,
+            10,
+        );
+        config.max_mint_per_period = 2 * fee;
+        config.mint_period = 10;
+        config.emission_budget = 5 * fee;
+        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+
+        // Pay twice within the first period.
+        // Expect no error.
+        for _ in 0..2 {
+            assert_ok!(PeaqMor::pay_machine_usage(
+                RuntimeOrigin::signed(muser),
+                machine,
+                fee
+            ));
+        }
+        System::assert_has_event(
+            crate::Event::<Test>::MintedTokens {
+                account: machine,
+                amount: fee,
+                remaining_in_period: 0,
+                remaining_budget: 3 * fee,
+            }
+            .into(),
+        );
+
+        // Try to pay a third time within the same period.
+        // Expect error MintPeriodCapReached.
+        assert_noop!(
+            PeaqMor::pay_machine_usage(RuntimeOrigin::signed(muser), machine, fee),
+            Error::<Test>::MintPeriodCapReached
+        );
+
+        // Within the next periods it is possible again, until the budget is exhausted.
+        // Expect no error.
+        System::set_block_number(10);
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            fee
+        ));
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            fee
+        ));
+        System::set_block_number(20);
+        assert_ok!(PeaqMor::pay_machine_usage(
+            RuntimeOrigin::signed(muser),
+            machine,
+            fee
+        ));
+        assert_eq!(PeaqMor::total_emission_of(), 5 * fee);
+
+        // Try to pay after the lifetime emission budget has been exhausted.
+        // Expect error EmissionBudgetExhausted.
+        assert_noop!(
+            PeaqMor::pay_machine_usage(RuntimeOrigin::signed(muser), machine, fee),
+            Error::<Test>::EmissionBudgetExhausted
+        );
+    });
+}
+
+#[test]
+fn transfer_from_pot_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Balances::free_balance(pot);
+
+        // Try to transfer the whole pot, which would reap the pot's account.
+        // Expect error InsufficientTokensInPot.
+        assert_noop!(
+            PeaqMor::transfer_from_pot(&owner, pot_balance),
+            Error::<Test>::InsufficientTokensInPot
+        );
+
+        // Transfer everything except the existential deposit, which is no reward.
+        // Expect no error.
+        assert_ok!(PeaqMor::transfer_from_pot(
+            &owner,
+            pot_balance - ExistentialDeposit::get()
+        ));
+        assert_eq!(Balances::free_balance(pot), ExistentialDeposit::get());
+        assert_eq!(PeaqMor::mor_metrics_of().paid_from_pot, 0);
+    });
+}
+
+#[test]
+fn pot_solvency_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Balances::free_balance(pot);
+        let available = BalanceOf::<Test>::from(1_000_000_000_000_000_000u128);
+
+        // Keep everything except 1 token as reserve, low threshold at 2 tokens.
+        let mut config = def_config(
+            BalanceOf::<Test>::from(REG_FEE),
+            BalanceOf::<Test>::from(REG_FEE),
+            BalanceOf::<Test>::from(3_000_000_000_000_000_000u128),
+            10,
+        );
+        config.pot_reserve = pot_balance - available;
+        config.pot_low_threshold = 2 * available;
+        assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+
+        // Try to transfer more than the amount above the reserve floor.
+        // Expect error InsufficientTokensInPot.
+        assert_noop!(
+            PeaqMor::transfer_from_pot(&owner, available + 1),
+            Error::<Test>::InsufficientTokensInPot
+        );
+
+        // Logging block-rewards detects the low pot.
+        // Expect event PotLow.
+        PeaqMor::log_block_rewards(BalanceOf::<Test>::from(400_000_000_000_000_000u128));
+        System::assert_last_event(crate::Event::<Test>::PotLow { available }.into());
+        assert!(PeaqMor::is_pot_low());
+
+        // Online rewards will be scaled down by available / threshold = 1/2.
+        // Expect no error.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        let owner_balance = Balances::free_balance(owner);
+        assert_ok!(PeaqMor::get_online_rewards(
+            RuntimeOrigin::signed(owner),
+            machine
+        ));
+        let paid = BalanceOf::<Test>::from(200_000_000_000_000_000u128);
+        assert_eq!(Balances::free_balance(owner), owner_balance + paid);
+        System::assert_last_event(
+            crate::Event::<Test>::OnlineRewardPaid {
+                owner,
+                machine,
+                amount: paid,
+                period: PeaqMor::current_period(),
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(pot), pot_balance - paid);
+    });
+}
+
+#[test]
+fn withdraw_from_pot_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+        let treasury = account_key("Treasury");
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Balances::free_balance(pot);
+        let amount = BalanceOf::<Test>::from(1_000_000_000_000_000_000u128);
+
+        // Try to withdraw from the pot as regular user.
+        // Expect error BadOrigin.
+        assert_noop!(
+            PeaqMor::withdraw_from_pot(RuntimeOrigin::signed(muser), muser, amount),
+            BadOrigin
+        );
+
+        // Try to withdraw more than the pot holds.
+        // Expect error InsufficientTokensInPot.
+        assert_noop!(
+            PeaqMor::withdraw_from_pot(RuntimeOrigin::root(), treasury, pot_balance),
+            Error::<Test>::InsufficientTokensInPot
+        );
+
+        // Withdraw to the treasury.
+        // Expect no error.
+        assert_ok!(PeaqMor::withdraw_from_pot(
+            RuntimeOrigin::root(),
+            treasury,
+            amount
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::PotWithdrawn {
+                destination: treasury,
+                amount,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(treasury), amount);
+        assert_eq!(Balances::free_balance(pot), pot_balance - amount);
+    });
+}
+
+#[test]
+fn donate_to_pot_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Balances::free_balance(pot);
+        let amount = BalanceOf::<Test>::from(1_000_000_000_000_000_000u128);
+
+        // Try to donate less than the existential deposit.
+        // Expect error DonationTooSmall.
+        assert_noop!(
+            PeaqMor::donate_to_pot(RuntimeOrigin::signed(muser), ExistentialDeposit::get() - 1),
+            Error::<Test>::DonationTooSmall
+        );
+
+        // Donate to the pot, the donation will be added to the latest block-reward.
+        // Expect no error.
+        PeaqMor::log_block_rewards(REG_FEE);
+        let record = PeaqMor::rewards_record_of();
+        assert_ok!(PeaqMor::donate_to_pot(RuntimeOrigin::signed(muser), amount));
+        System::assert_last_event(
+            crate::Event::<Test>::PotDonated {
+                donor: muser,
+                amount,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(pot), pot_balance + amount);
+        assert_eq!(PeaqMor::period_reward_of(), REG_FEE + amount);
+
+        // Donations do not advance the ring buffer of block-rewards, so they cannot push
+        // collected block-rewards out of it.
+        assert_ok!(PeaqMor::donate_to_pot(RuntimeOrigin::signed(muser), amount));
+        let (slot_cnt, balances) = PeaqMor::rewards_record_of();
+        assert_eq!(slot_cnt, record.0);
+        assert!(balances.contains(&(REG_FEE + 2 * amount)));
+        assert_eq!(PeaqMor::period_reward_of(), REG_FEE + 2 * amount);
+    });
+}
+
+#[test]
+fn sweep_pot_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let amount = BalanceOf::<Test>::from(9_000_000_000_000_000_000u128);
+
+        // Pot is below its ceiling, nothing will be swept.
+        let pot_balance = Balances::free_balance(pot);
+        PeaqMor::on_idle(1, Weight::MAX);
+        assert_eq!(Balances::free_balance(pot), pot_balance);
+
+        // Donate above the ceiling, the surplus will be swept in idle time.
+        // Expect event PotSwept.
+        assert_ok!(PeaqMor::donate_to_pot(RuntimeOrigin::signed(owner), amount));
+        assert_ok!(PeaqMor::donate_to_pot(RuntimeOrigin::signed(muser), amount));
+        let surplus = pot_balance + 2 * amount - POT_CEILING;
+        let issuance = Balances::total_issuance();
+        PeaqMor::on_idle(1, Weight::MAX);
+        System::assert_last_event(crate::Event::<Test>::PotSwept { amount: surplus }.into());
+        assert_eq!(Balances::free_balance(pot), POT_CEILING);
+        assert_eq!(Balances::total_issuance(), issuance - surplus);
+
+        // Without enough remaining weight, nothing will be swept.
+        assert_eq!(PeaqMor::on_idle(1, Weight::zero()), Weight::zero());
+    });
+}
+
+fn def_class() -> CampaignEligibility {
+    CampaignEligibility::MachineClass(
+        M_ATTR.to_vec().try_into().unwrap(),
+        M_VAL.to_vec().try_into().unwrap(),
+    )
+}
+
+fn create_campaign_mor(budget: u128, reward_rate: u128, eligibility: CampaignEligibility) {
+    // Create a campaign, which runs from block 1 to block 100.
+    // Expect no error.
+    assert_ok!(PeaqMor::create_campaign(
+        RuntimeOrigin::root(),
+        CAMPAIGN_NAME.to_vec().try_into().unwrap(),
+        BalanceOf::<Test>::from(budget),
+        BalanceOf::<Test>::from(reward_rate),
+        10,
+        1,
+        100,
+        eligibility
+    ));
+}
+
+#[test]
+fn create_campaign_test() {
+    new_test_ext().execute_with(|| {
+        let muser = account_key(U_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Balances::free_balance(pot);
+        let budget = 10 * REG_FEE;
+        let name: frame_support::BoundedVec<_, _> = CAMPAIGN_NAME.to_vec().try_into().unwrap();
+
+        // Try to create a campaign as regular user.
+        // Expect error BadOrigin.
+        assert_noop!(
+            PeaqMor::create_campaign(
+                RuntimeOrigin::signed(muser),
+                name.clone(),
+                budget,
+                REG_FEE,
+                10,
+                1,
+                100,
+                def_class()
+            ),
+            BadOrigin
+        );
+
+        // Try to create a campaign, which ends before it starts.
+        // Expect error CampaignIsNotConsistent.
+        assert_noop!(
+            PeaqMor::create_campaign(
+                RuntimeOrigin::root(),
+                name.clone(),
+                budget,
+                REG_FEE,
+                10,
+                100,
+                1,
+                def_class()
+            ),
+            Error::<Test>::CampaignIsNotConsistent
+        );
+
+        // Try to create a campaign with a larger budget than the pot holds.
+        // Expect error InsufficientTokensInPot.
+        assert_noop!(
+            PeaqMor::create_campaign(
+                RuntimeOrigin::root(),
+                name,
+                pot_balance,
+                REG_FEE,
+                10,
+                1,
+                100,
+                def_class()
+            ),
+            Error::<Test>::InsufficientTokensInPot
+        );
+
+        // Create two campaigns, each gets its own pot funded from the pallet's pot.
+        create_campaign_mor(budget, REG_FEE, def_class());
+        create_campaign_mor(budget, REG_FEE, CampaignEligibility::AllMachines);
+        let account = PeaqMor::campaign_account(1);
+        System::assert_last_event(
+            crate::Event::<Test>::CampaignCreated {
+                campaign_id: 1,
+                pot: account,
+                budget,
+            }
+            .into(),
+        );
+        assert_eq!(PeaqMor::next_campaign_id(), 2);
+        assert_ne!(PeaqMor::campaign_account(0), account);
+        assert_eq!(Balances::free_balance(PeaqMor::campaign_account(0)), budget);
+        assert_eq!(Balances::free_balance(account), budget);
+        assert_eq!(Balances::free_balance(pot), pot_balance - 2 * budget);
+    });
+}
+
+#[test]
+fn claim_campaign_reward_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let other = account_key("RPi002");
+
+        create_campaign_mor(2 * REG_FEE, REG_FEE, def_class());
+        create_campaign_mor(
+            2 * REG_FEE,
+            REG_FEE,
+            CampaignEligibility::DidAttribute(b"Firmware".to_vec().try_into().unwrap()),
+        );
+
+        // Try to claim for a machine, which is not registered.
+        // Expect error DidAuthorizationFailed.
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 0, machine),
+            Error::<Test>::DidAuthorizationFailed
+        );
+
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+
+        // Try to claim from a campaign, which does not exist.
+        // Expect error CampaignNotFound.
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 2, machine),
+            Error::<Test>::CampaignNotFound
+        );
+
+        // Try to claim from a campaign, which requires another attribute.
+        // Expect error MachineNotEligible.
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 1, machine),
+            Error::<Test>::MachineNotEligible
+        );
+
+        // Claim the campaign's reward, which counts as paid from the pot, unlike the
+        // campaign's budget.
+        // Expect no error.
+        assert_eq!(PeaqMor::mor_metrics_of().paid_from_pot, 0);
+        let balance = Balances::free_balance(owner);
+        assert_ok!(PeaqMor::claim_campaign_reward(
+            RuntimeOrigin::signed(owner),
+            0,
+            machine
+        ));
+        System::assert_last_event(
+            crate::Event::<Test>::CampaignRewardPaid {
+                campaign_id: 0,
+                owner,
+                machine,
+                amount: REG_FEE,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(owner), balance + REG_FEE);
+        assert_eq!(PeaqMor::mor_metrics_of().paid_from_pot, REG_FEE);
+        assert_eq!(
+            PeaqMor::campaign_claim_of(0, machine_hash(machine)),
+            Some(1)
+        );
+
+        // Try to claim again within the claim interval.
+        // Expect error CampaignRewardAlreadyClaimed.
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 0, machine),
+            Error::<Test>::CampaignRewardAlreadyClaimed
+        );
+
+        // Claim for a second machine, now the budget is used up.
+        register_machine_did(owner, other);
+        get_registration_reward_mor(owner, other);
+        assert_ok!(PeaqMor::claim_campaign_reward(
+            RuntimeOrigin::signed(owner),
+            0,
+            other
+        ));
+
+        // Try to claim after the claim interval, but with an exhausted budget.
+        // Expect error CampaignBudgetExhausted.
+        System::set_block_number(11);
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 0, machine),
+            Error::<Test>::CampaignBudgetExhausted
+        );
+
+        // Try to claim after the campaign has ended.
+        // Expect error CampaignNotActive.
+        System::set_block_number(100);
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 0, machine),
+            Error::<Test>::CampaignNotActive
+        );
+    });
+}
+
+#[test]
+fn claim_campaign_reward_frozen_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let muser = account_key(U_ACCT);
+        let machine = account_key(M_ACCT);
+
+        create_campaign_mor(2 * REG_FEE, REG_FEE, def_class());
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+
+        // Confirm a misbehaviour of the machine, its rewards will be frozen until block 21.
+        report_misbehaviour_mor(muser, machine);
+        assert_ok!(PeaqMor::adjudicate_misbehaviour(
+            RuntimeOrigin::root(),
+            machine,
+            true
+        ));
+
+        // Try to claim the campaign's reward while the machine's rewards are frozen.
+        // Expect error MachineRewardsFrozen.
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 0, machine),
+            Error::<Test>::MachineRewardsFrozen
+        );
+
+        // Claim after the machine's rewards have been unfrozen.
+        // Expect no error.
+        System::set_block_number(21);
+        assert_ok!(PeaqMor::claim_campaign_reward(
+            RuntimeOrigin::signed(owner),
+            0,
+            machine
+        ));
+    });
+}
+
+#[test]
+fn close_campaign_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let pot = PotId::get().into_account_truncating();
+        let pot_balance = Balances::free_balance(pot);
+
+        create_campaign_mor(10 * REG_FEE, REG_FEE, CampaignEligibility::AllMachines);
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        assert_ok!(PeaqMor::claim_campaign_reward(
+            RuntimeOrigin::signed(owner),
+            0,
+            machine
+        ));
+
+        // Try to close the campaign as regular user.
+        // Expect error BadOrigin.
+        assert_noop!(
+            PeaqMor::close_campaign(RuntimeOrigin::signed(owner), 0),
+            BadOrigin
+        );
+
+        // Close the campaign, the remaining budget goes back to the pot.
+        // Expect no error.
+        assert_ok!(PeaqMor::close_campaign(RuntimeOrigin::root(), 0));
+        System::assert_last_event(
+            crate::Event::<Test>::CampaignClosed {
+                campaign_id: 0,
+                returned: 9 * REG_FEE,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(PeaqMor::campaign_account(0)), 0);
+        assert_eq!(Balances::free_balance(pot), pot_balance - REG_FEE);
+        assert_eq!(PeaqMor::campaign_of(0), None);
+
+        // Try to claim from or close the closed campaign.
+        // Expect error CampaignNotFound.
+        assert_noop!(
+            PeaqMor::claim_campaign_reward(RuntimeOrigin::signed(owner), 0, machine),
+            Error::<Test>::CampaignNotFound
+        );
+        assert_noop!(
+            PeaqMor::close_campaign(RuntimeOrigin::root(), 0),
+            Error::<Test>::CampaignNotFound
+        );
+        assert_eq!(PeaqMor::next_campaign_id(), 1);
+    });
+}
+
+#[test]
+fn log_block_rewards_test() {
+    new_test_ext().execute_with(|| {
+        let balance = BalanceOf::<Test>::from(100_000_000_000_000_000u128);
+        // Try to log new block-reward in default genesis configuration.
+        // Expect no error.
+        PeaqMor::log_block_rewards(balance);
+    });
+}
+
+fn seed_reward_record(slot_cnt: u8, balances: Vec<u128>) {
+    let balances: BoundedVec<u128, ConstU32<255>> = BoundedVec::truncate_from(balances);
+    let period_reward = balances.iter().sum::<u128>();
+    RewardsRecordStorage::<Test>::put((slot_cnt, balances));
+    PeriodRewardStorage::<Test>::put(period_reward);
+}
+
+fn run_migrations() {
+    // Run all migrations, with try-runtime including the pre- and post-upgrade checks.
+    // Expect no error.
+    #[cfg(feature = "try-runtime")]
+    assert_ok!(Migrations::<Test>::try_on_runtime_upgrade(true));
+    #[cfg(not(feature = "try-runtime"))]
+    Migrations::<Test>::on_runtime_upgrade();
+    assert_eq!(PeaqMor::on_chain_storage_version(), 4);
+}
+
+#[test]
+fn migrate_from_v2_test() {
+    new_test_ext().execute_with(|| {
+        // Seed the config in the layout up to version 3 and a ring buffer, which does not
+        // match the tracked number of block-rewards.
+        StorageVersion::new(2).put::<PeaqMor>();
+        let old_config = (
+            Compact(REG_FEE),
+            Compact(REG_FEE),
+            Compact(2 * REG_FEE),
+            Compact(5u8),
+        );
+        unhashed::put_raw(
+            &MorConfigStorage::<Test>::hashed_key(),
+            &old_config.encode(),
+        );
+        seed_reward_record(1, vec![1, 2, 3]);
+
+        run_migrations();
+
+        // Expect the config to be kept and the ring buffer to be rebuilt.
+        let config = PeaqMor::mor_config_of();
+        assert_eq!(config.registration_reward, REG_FEE);
+        assert_eq!(config.machine_usage_fee_min, REG_FEE);
+        assert_eq!(config.machine_usage_fee_max, 2 * REG_FEE);
+        assert_eq!(config.track_n_block_rewards, 5);
+        assert_eq!(config.max_mint_per_period, u128::MAX);
+        assert_eq!(config.mint_period, 5);
+        let (slot_cnt, balances) = PeaqMor::rewards_record_of();
+        assert_eq!(slot_cnt, 0);
+        assert_eq!(balances.to_vec(), vec![0; 5]);
+        assert_eq!(PeaqMor::period_reward_of(), 0);
+    });
+}
+
+#[test]
+fn migrate_from_v3_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+
+        // Seed the config in the layout of version 3, a matching ring buffer and two
+        // registered machines, which have not been counted.
+        StorageVersion::new(3).put::<PeaqMor>();
+        let old_config = (
+            Compact(REG_FEE),
+            Compact(REG_FEE),
+            Compact(2 * REG_FEE),
+            Compact(3u8),
+        );
+        unhashed::put_raw(
+            &MorConfigStorage::<Test>::hashed_key(),
+            &old_config.encode(),
+        );
+        seed_reward_record(1, vec![1, 2, 3]);
+        for machine in [account_key(M_ACCT), account_key("RPi002")] {
+            MachineRegister::<Test>::insert(machine_hash(machine), machine_hash(owner));
+        }
+
+        run_migrations();
+
+        // Expect the config to be extended by uncapped minting and an empty reserve, the
+        // ring buffer to be kept and the machines to be counted in the metrics.
+        let config = PeaqMor::mor_config_of();
+        assert_eq!(config.registration_reward, REG_FEE);
+        assert_eq!(config.machine_usage_fee_max, 2 * REG_FEE);
+        assert_eq!(config.track_n_block_rewards, 3);
+        assert_eq!(config.max_mint_per_period, u128::MAX);
+        assert_eq!(config.mint_period, 3);
+        assert_eq!(config.emission_budget, u128::MAX);
+        assert_eq!(config.pot_reserve, 0);
+        assert_eq!(config.pot_low_threshold, 0);
+        let (slot_cnt, balances) = PeaqMor::rewards_record_of();
+        assert_eq!(slot_cnt, 1);
+        assert_eq!(balances.to_vec(), vec![1, 2, 3]);
+        assert_eq!(PeaqMor::period_reward_of(), 6);
+        assert_eq!(PeaqMor::mor_metrics_of().registered_machines, 2);
+        assert!(
+            PeaqMor::machine_tombstone_of(machine_hash(account_key(M_ACCT))).registration_rewarded
+        );
+    });
+}
+
+#[test]
+fn migrate_undecodable_config_test() {
+    for version in [2, 3] {
+        new_test_ext().execute_with(|| {
+            // Seed a config, which cannot be decoded in the layout of its version.
+            // Its pre-upgrade check would fail, so the migrations run without them.
+            StorageVersion::new(version).put::<PeaqMor>();
+            unhashed::put_raw(&MorConfigStorage::<Test>::hashed_key(), &[0xff]);
+            Migrations::<Test>::on_runtime_upgrade();
+            assert_eq!(PeaqMor::on_chain_storage_version(), 4);
+
+            // Expect the config to be reset to the default, which can be decoded again.
+            let config = MorConfig::<u128>::default();
+            assert_eq!(
+                unhashed::get_raw(&MorConfigStorage::<Test>::hashed_key()),
+                Some(config.encode())
+            );
+            assert_eq!(PeaqMor::mor_config_of(), config);
+        });
+    }
+}
+
+#[test]
+fn migrate_current_version_test() {
+    new_test_ext().execute_with(|| {
+        // Run the migrations on the current version.
+        // Expect nothing to be changed.
+        let config = PeaqMor::mor_config_of();
+        let record = PeaqMor::rewards_record_of();
+        run_migrations();
+        assert_eq!(PeaqMor::mor_config_of(), config);
+        assert_eq!(PeaqMor::rewards_record_of(), record);
+    });
+}
+
+#[test]
+fn try_state_test() {
+    new_test_ext().execute_with(|| {
+        let owner = account_key(O_ACCT);
+        let machine = account_key(M_ACCT);
+        let other = account_key("RPi002");
+
+        // Check the genesis state and a registered machine.
+        // Expect no error.
+        register_machine_did(owner, machine);
+        get_registration_reward_mor(owner, machine);
+        PeaqMor::log_block_rewards(REG_FEE);
+        assert_ok!(PeaqMor::do_try_state());
+        assert_eq!(
+            PeaqMor::machine_accounts_of(machine_hash(machine)),
+            Some((machine, owner))
+        );
+
+        // A machine registered by an older version, which is not counted in the metrics.
+        // Expect an error.
+        MachineRegister::<Test>::insert(machine_hash(other), machine_hash(owner));
+        assert!(PeaqMor::do_try_state().is_err());
+
+        // Once counted, it cannot be verified, but is flagged.
+        // Expect no error.
+        MorMetricsStorage::<Test>::mutate(|metrics| metrics.registered_machines += 1);
+        assert_ok!(PeaqMor::do_try_state());
+
+        // A registered machine, which is not owned in Peaq-DID anymore, is flagged too.
+        // Expect no error.
+        MachineAccounts::<Test>::insert(machine_hash(other), (other, owner));
+        assert_ok!(PeaqMor::do_try_state());
+
+        // Accounts, which do not match the registration.
+        // Expect an error.
+        MachineAccounts::<Test>::insert(machine_hash(other), (machine, owner));
+        assert!(PeaqMor::do_try_state().is_err());
+        MachineAccounts::<Test>::remove(machine_hash(other));
+
+        // The period reward does not match the ring buffer.
+        // Expect an error.
+        PeriodRewardStorage::<Test>::put(0u128);
+        assert!(PeaqMor::do_try_state().is_err());
+
+        // The ring buffer does not match the tracked number of block-rewards.
+        // Expect an error.
+        seed_reward_record(0, vec![]);
+        assert!(PeaqMor::do_try_state().is_err());
+    });
+}
+
+fn set_track_n_block_rewards(track_n_block_rewards: u8) {
+    // Change the tracked number of block-rewards via the configuration.
+    // Expect no error.
+    let mut config = PeaqMor::mor_config_of();
+    config.track_n_block_rewards = track_n_block_rewards;
+    assert_ok!(PeaqMor::set_configuration(RuntimeOrigin::root(), config));
+}
+
+fn chronological_rewards() -> Vec<u128> {
+    let (slot_cnt, balances) = PeaqMor::rewards_record_of();
+    let mut balances = balances.to_vec();
+    balances.rotate_left(slot_cnt as usize);
+    balances
+}
+
+#[test]
+fn resize_track_storage_test() {
+    new_test_ext().execute_with(|| {
+        set_track_n_block_rewards(4);
+        for amount in 1..=6u128 {
+            PeaqMor::log_block_rewards(amount);
+        }
+        // The ring buffer is [5, 6, 3, 4] with the oldest block-reward in slot 2.
+        assert_eq!(PeaqMor::rewards_record_of().0, 2);
+        assert_eq!(chronological_rewards(), vec![3, 4, 5, 6]);
+
+        // Shrink the window, expect the most recent block-rewards to be kept.
+        set_track_n_block_rewards(2);
+        assert_eq!(PeaqMor::rewards_record_of().1.to_vec(), vec![5, 6]);
+        assert_eq!(PeaqMor::period_reward_of(), 11);
+
+        // Grow the window, expect empty slots to be the oldest ones.
+        set_track_n_block_rewards(3);
+        assert_eq!(chronological_rewards(), vec![0, 5, 6]);
+        assert_eq!(PeaqMor::period_reward_of(), 11);
+
+        // The next block-reward replaces the oldest one.
+        PeaqMor::log_block_rewards(7);
+        assert_eq!(chronological_rewards(), vec![5, 6, 7]);
+        assert_eq!(PeaqMor::period_reward_of(), 18);
+        assert_ok!(PeaqMor::do_try_state());
+    });
+}
+
+#[derive(Clone, Debug)]
+enum RecordOp {
+    Log(u128),
+    Resize(u8),
+}
+
+fn record_op() -> impl Strategy<Value = RecordOp> {
+    prop_oneof![
+        3 => (1u128..1_000_000u128).prop_map(RecordOp::Log),
+        1 => (1u8..=20u8).prop_map(RecordOp::Resize),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn resize_track_storage_matches_model(ops in prop::collection::vec(record_op(), 0..100)) {
+        new_test_ext().execute_with(|| {
+            // Reference model: the tracked block-rewards in chronological order.
+            let track_n_block_rewards = PeaqMor::mor_config_of().track_n_block_rewards;
+            let mut model: VecDeque<u128> = vec![0; track_n_block_rewards as usize].into();
+
+            for op in ops {
+                match op {
+                    RecordOp::Log(amount) => {
+                        PeaqMor::log_block_rewards(amount);
+                        model.pop_front();
+                        model.push_back(amount);
+                    }
+                    RecordOp::Resize(new_size) => {
+                        set_track_n_block_rewards(new_size);
+                        while model.len() > new_size as usize {
+                            model.pop_front();
+                        }
+                        while model.len() < new_size as usize {
+                            model.push_front(0);
+                        }
+                    }
+                }
+                assert_eq!(chronological_rewards(), Vec::from(model.clone()));
+                assert_eq!(PeaqMor::period_reward_of(), model.iter().sum::<u128>());
+                assert_ok!(PeaqMor::do_try_state());
+            }
+        });
+    }
 }
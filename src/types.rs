@@ -1,19 +1,76 @@
 //! All pallet relevant structs are defined here
 
-use frame_support::traits::{tokens::Balance as BalanceT, Currency};
+use frame_support::{
+    traits::{fungible, fungibles, tokens::Balance as BalanceT, ConstU32},
+    BoundedVec,
+};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_core::RuntimeDebug;
+use sp_runtime::{
+    traits::{IntegerSquareRoot, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+    FixedPointNumber, FixedU128, Perbill,
+};
+use sp_std::vec::Vec;
 
 /// Short form type definition to simplify method definition.
-pub type BalanceOf<T> =
-    <<T as crate::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type BalanceOf<T> = <<T as crate::Config>::Currency as fungible::Inspect<
+    <T as frame_system::Config>::AccountId,
+>>::Balance;
+/// Short form type definition to simplify method definition.
+pub type AssetIdOf<T> = <<T as crate::Config>::Assets as fungibles::Inspect<
+    <T as frame_system::Config>::AccountId,
+>>::AssetId;
+/// Short form type definition to simplify method definition.
+pub type MachineBondOf<T> = MachineBond<
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Config>::BlockNumber,
+>;
+/// Identifier of a reward campaign.
+pub type CampaignId = u32;
+/// Maximum length of a campaign's name.
+pub const MAX_NAME_LEN: u32 = 32;
+/// Maximum length of names and values of Peaq-DID attributes, which are referenced here.
+pub const MAX_ATTR_LEN: u32 = 64;
+/// Maximum length of a geohash, 12 characters locate a machine within a few centimetres.
+pub const MAX_GEOHASH_LEN: u32 = 12;
+/// Maximum number of eligibility rules, which can be configured by governance.
+pub const MAX_ELIGIBILITY_RULES: u32 = 8;
+/// Maximum length of the evidence of a misbehaviour report.
+pub const MAX_EVIDENCE_LEN: u32 = 256;
+/// Maximum length of a machine's endpoint, which will be pinged by the offchain worker.
+pub const MAX_ENDPOINT_LEN: u32 = 128;
+/// Prefix of the payload, which machines sign for their heartbeats, see `Heartbeat`.
+pub const HEARTBEAT_CONTEXT: &[u8] = b"peaq-mor/heartbeat";
+/// Alphabet of geohashes (base32 without "a", "i", "l" and "o").
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
 /// Short form type definition to simplify method definition. This definition is neccessary
 /// due to the tight coupling of another pallet (Peaq-DID).
 pub type WeightOf<T> = <T as crate::Config>::WeightInfo;
 
+/// Reasons for which this pallet places holds or freezes on funds of an account. The
+/// runtime's hold reason has to be convertible from this, see `Config::RuntimeHoldReason`.
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum HoldReason {
+    /// Funds are bonded behind a registered machine.
+    MachineBond,
+}
+
 /// This struct defines the configurable paramters of the Peaq-MOR pallet. All contained
 /// parameters can be configured by a dispatchable function (extrinsic).
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -34,6 +91,23 @@ where
     /// Defines how much how much block rewards will be tracked in the past (to build a sum of them)
     #[codec(compact)]
     pub track_n_block_rewards: u8,
+    /// Maximum amount of tokens, which can be minted within one minting period (registration
+    /// rewards and machine usage payments together)
+    #[codec(compact)]
+    pub max_mint_per_period: Balance,
+    /// Length of one minting period in blocks
+    #[codec(compact)]
+    pub mint_period: u32,
+    /// Maximum amount of tokens, which can be minted by this pallet over its whole lifetime
+    #[codec(compact)]
+    pub emission_budget: Balance,
+    /// Minimum amount of tokens, which the pot always has to keep (reserve floor)
+    #[codec(compact)]
+    pub pot_reserve: Balance,
+    /// If the pot's balance above the reserve floor falls below this threshold, online
+    /// rewards will be scaled down proportionally and the pot is considered to be low
+    #[codec(compact)]
+    pub pot_low_threshold: Balance,
 }
 
 impl<Balance: BalanceT> MorConfig<Balance> {
@@ -44,8 +118,11 @@ impl<Balance: BalanceT> MorConfig<Balance> {
         let range_usage = self.machine_usage_fee_max > self.machine_usage_fee_min;
         let range_min = self.registration_reward > existential_deposit
             && self.machine_usage_fee_min > existential_deposit;
+        // a registration reward has to fit into a minting period, and the period length
+        // is a divisor, therefor not allowed to be zero!
+        let minting = self.max_mint_per_period >= self.registration_reward && self.mint_period > 0;
 
-        blocks && range_usage && range_min
+        blocks && range_usage && range_min && minting
     }
 }
 
@@ -55,10 +132,586 @@ impl<Balance: BalanceT> Default for MorConfig<Balance> {
             // Because Balance can only be set to zero to keep the pallet as generic
             // as possible - set every parameter to zero! Except for track_n_block_rewards!
             // -> an initial configuration has to be done in Genesis or after deployment...
+            // Minting is not capped by default, to keep the behaviour of older versions.
             registration_reward: Balance::zero(),
             machine_usage_fee_min: Balance::zero(),
             machine_usage_fee_max: Balance::one(),
             track_n_block_rewards: 1,
+            max_mint_per_period: Balance::max_value(),
+            mint_period: 1,
+            emission_budget: Balance::max_value(),
+            pot_reserve: Balance::zero(),
+            pot_low_threshold: Balance::zero(),
+        }
+    }
+}
+
+/// Currency in which a machine's tariff is denominated.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TariffCurrency<AssetId> {
+    /// The network's native token, see `Config::Currency`.
+    Native,
+    /// A whitelisted fungible asset, e.g. a stablecoin, see `Config::Assets`.
+    Asset(AssetId),
+}
+
+impl<AssetId> Default for TariffCurrency<AssetId> {
+    fn default() -> Self {
+        TariffCurrency::Native
+    }
+}
+
+/// This struct defines the limits of machine usage payments for one whitelisted asset,
+/// like `machine_usage_fee_min` and `machine_usage_fee_max` in `MorConfig` do for the
+/// native currency. Only whitelisted assets can be used for payments. The reward rate
+/// converts online rewards into the asset, if it has been selected as reward asset.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetConfig<Balance>
+where
+    Balance: BalanceT + MaxEncodedLen,
+{
+    /// Minimum balance limit for machine usage payments in this asset
+    #[codec(compact)]
+    pub machine_usage_fee_min: Balance,
+    /// Maximum balance limit for machine usage payments in this asset
+    #[codec(compact)]
+    pub machine_usage_fee_max: Balance,
+    /// Amount of this asset, which will be paid out instead of one unit of the native
+    /// currency. Zero, if the asset cannot be used for online rewards.
+    pub reward_rate: FixedU128,
+}
+
+impl<Balance: BalanceT> AssetConfig<Balance> {
+    /// Method checks whether configuration is consistent, given the asset's minimum balance.
+    pub fn is_consistent(&self, minimum_balance: Balance) -> bool {
+        self.machine_usage_fee_max > self.machine_usage_fee_min
+            && self.machine_usage_fee_min >= minimum_balance
+    }
+
+    /// Method converts an amount of the native currency into this asset by the reward rate.
+    pub fn convert(&self, amount: Balance) -> Balance {
+        let amount: u128 = amount.unique_saturated_into();
+        Balance::unique_saturated_from(self.reward_rate.saturating_mul_int(amount))
+    }
+
+    /// Method converts an amount of this asset into the native currency by the reward rate,
+    /// the inverse of `convert`. Zero, if the asset has no reward rate.
+    pub fn to_native(&self, amount: Balance) -> Balance {
+        let amount: u128 = amount.unique_saturated_into();
+        self.reward_rate
+            .reciprocal()
+            .map_or(Balance::zero(), |rate| {
+                Balance::unique_saturated_from(rate.saturating_mul_int(amount))
+            })
+    }
+}
+
+/// This struct defines the usage tariff of a single machine. It is published by the
+/// machine's owner and has to be within the global usage fee limits of `MorConfig`.
+/// A payment for using the machine consists of the flat fee plus a multiple of the
+/// price per unit (e.g. per kWh or per minute).
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MachineTariff<Balance, AssetId>
+where
+    Balance: BalanceT + MaxEncodedLen,
+{
+    /// Fixed amount, which has to be paid for each usage of the machine.
+    #[codec(compact)]
+    pub flat_fee: Balance,
+    /// Price per consumed unit, on top of the flat fee.
+    #[codec(compact)]
+    pub unit_price: Balance,
+    /// Currency in which the tariff is denominated.
+    pub currency: TariffCurrency<AssetId>,
+}
+
+impl<Balance: BalanceT, AssetId> MachineTariff<Balance, AssetId> {
+    /// Method checks whether the tariff is within the given usage fee limits. The
+    /// cheapest possible usage (flat fee plus one unit) has to be within the range.
+    pub fn is_within(&self, fee_min: Balance, fee_max: Balance) -> bool {
+        let single_usage = self.flat_fee.saturating_add(self.unit_price);
+
+        single_usage >= fee_min && single_usage <= fee_max
+    }
+
+    /// Method checks whether the given amount is a valid payment according to this
+    /// tariff, i.e. the flat fee plus a whole number of units.
+    pub fn accepts(&self, amount: Balance) -> bool {
+        if amount < self.flat_fee {
+            return false;
+        }
+        if self.unit_price.is_zero() {
+            amount == self.flat_fee
+        } else {
+            ((amount - self.flat_fee) % self.unit_price).is_zero()
+        }
+    }
+}
+
+/// Rules, which machines have to fulfil to be eligible for rewards of a campaign.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CampaignEligibility {
+    /// Every machine, which is registered in Peaq-MOR.
+    AllMachines,
+    /// Machines, which have an attribute with the given name in Peaq-DID.
+    DidAttribute(BoundedVec<u8, ConstU32<MAX_ATTR_LEN>>),
+    /// Machines of a class, i.e. which have the given attribute with the given value in
+    /// Peaq-DID, e.g. "Type" = "EV-Charger".
+    MachineClass(
+        BoundedVec<u8, ConstU32<MAX_ATTR_LEN>>,
+        BoundedVec<u8, ConstU32<MAX_ATTR_LEN>>,
+    ),
+}
+
+/// This struct defines a reward campaign (incentive program). Each campaign has its own
+/// pot (a sub-account of the pallet's pot), its own budget and eligibility rules. Machine
+/// owners can claim the reward rate once per claim interval for each eligible machine,
+/// between the start and the end block of the campaign.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Campaign<Balance, BlockNumber>
+where
+    Balance: BalanceT + MaxEncodedLen,
+{
+    /// Human readable name of the campaign.
+    pub name: BoundedVec<u8, ConstU32<MAX_NAME_LEN>>,
+    /// Total amount of tokens, which can be paid out by this campaign.
+    #[codec(compact)]
+    pub budget: Balance,
+    /// Amount of tokens, which have been paid out so far.
+    #[codec(compact)]
+    pub paid: Balance,
+    /// Amount of tokens a machine owner gets per claim.
+    #[codec(compact)]
+    pub reward_rate: Balance,
+    /// Number of blocks, a machine has to wait between two claims.
+    pub claim_interval: BlockNumber,
+    /// First block in which rewards can be claimed.
+    pub start: BlockNumber,
+    /// First block in which rewards cannot be claimed anymore.
+    pub end: BlockNumber,
+    /// Rules, which machines have to fulfil to be eligible.
+    pub eligibility: CampaignEligibility,
+}
+
+impl<Balance: BalanceT, BlockNumber: PartialOrd + Zero> Campaign<Balance, BlockNumber> {
+    /// Method checks whether the campaign is consistent.
+    pub fn is_consistent(&self) -> bool {
+        self.start < self.end
+            && !self.claim_interval.is_zero()
+            && !self.reward_rate.is_zero()
+            && self.reward_rate <= self.budget
+    }
+
+    /// Method checks whether the campaign is active at the given block.
+    pub fn is_active(&self, now: BlockNumber) -> bool {
+        self.start <= now && now < self.end
+    }
+}
+
+/// This struct defines the metadata of a registered machine. It is published by the
+/// machine's owner and refers to the machine's document in Peaq-DID by an attribute's name.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MachineMetadata {
+    /// Type of the machine, e.g. "EV-Charger".
+    pub machine_type: BoundedVec<u8, ConstU32<MAX_NAME_LEN>>,
+    /// Location of the machine as geohash, e.g. "u33dc0".
+    pub geohash: BoundedVec<u8, ConstU32<MAX_GEOHASH_LEN>>,
+    /// Capacity of the machine, its unit depends on the machine's type (e.g. kW).
+    #[codec(compact)]
+    pub capacity: u32,
+    /// Version of the machine's firmware.
+    pub firmware: BoundedVec<u8, ConstU32<MAX_NAME_LEN>>,
+    /// Name of the machine's attribute in Peaq-DID, which this metadata refers to.
+    pub did_attribute: BoundedVec<u8, ConstU32<MAX_ATTR_LEN>>,
+    /// HTTP(S) endpoint of the machine, e.g. "https://rpi001.example.com/health", which
+    /// will be pinged by the offchain worker to attest the machine's liveness. Machines
+    /// without an endpoint will not be pinged.
+    pub endpoint: BoundedVec<u8, ConstU32<MAX_ENDPOINT_LEN>>,
+}
+
+impl MachineMetadata {
+    /// Method checks whether the metadata is consistent, i.e. the machine's type and the
+    /// referenced attribute are given, the geohash is valid and the endpoint, if given,
+    /// is an HTTP(S) URL.
+    pub fn is_consistent(&self) -> bool {
+        !self.machine_type.is_empty()
+            && !self.did_attribute.is_empty()
+            && self.geohash.iter().all(|c| GEOHASH_ALPHABET.contains(c))
+            && (self.endpoint.is_empty()
+                || (sp_std::str::from_utf8(&self.endpoint).is_ok()
+                    && (self.endpoint.starts_with(b"http://")
+                        || self.endpoint.starts_with(b"https://"))))
+    }
+}
+
+/// This struct defines a rule, which a machine has to fulfil to be registered and to
+/// receive online rewards. The machine's document in Peaq-DID has to contain a valid
+/// (non-expired) attribute with the given name and, if given, with the given value.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EligibilityRule {
+    /// Name of the required attribute in Peaq-DID.
+    pub name: BoundedVec<u8, ConstU32<MAX_ATTR_LEN>>,
+    /// Required value of the attribute, any value is accepted if not given.
+    pub value: Option<BoundedVec<u8, ConstU32<MAX_ATTR_LEN>>>,
+}
+
+impl EligibilityRule {
+    /// Method checks whether the rule is consistent, i.e. the attribute's name is given.
+    pub fn is_consistent(&self) -> bool {
+        !self.name.is_empty()
+    }
+}
+
+/// Set of eligibility rules, all of them have to be fulfilled.
+pub type EligibilityRules = BoundedVec<EligibilityRule, ConstU32<MAX_ELIGIBILITY_RULES>>;
+
+/// This struct defines an attribute of a machine's identity, as it will be provided by
+/// the `MachineIdentityProvider`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct MachineAttribute<BlockNumber> {
+    /// Value of the attribute.
+    pub value: Vec<u8>,
+    /// Block number until which the attribute is valid.
+    pub validity: BlockNumber,
+}
+
+/// This enum defines how the stake behind a machine weights its online rewards.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(
+    PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum StakeCurve {
+    /// The bonus grows linear with the stake.
+    #[default]
+    Linear,
+    /// The bonus grows with the square root of the stake, so small stakes count more.
+    Sqrt,
+}
+
+impl StakeCurve {
+    /// Method maps the ratio of a stake to the full stake onto the ratio of the bonus.
+    pub fn apply(&self, ratio: Perbill) -> Perbill {
+        match self {
+            StakeCurve::Linear => ratio,
+            StakeCurve::Sqrt => {
+                let parts = (ratio.deconstruct() as u64).saturating_mul(1_000_000_000);
+                Perbill::from_parts(parts.integer_sqrt() as u32)
+            }
+        }
+    }
+}
+
+/// This struct defines, how machine owners will be rewarded for bonding tokens behind
+/// their machines. Without a full stake, staking does not affect online rewards.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StakingConfig<Balance, BlockNumber> {
+    /// Curve, which maps the stake onto the bonus.
+    pub curve: StakeCurve,
+    /// Stake, which earns the maximum bonus. Larger stakes do not earn more.
+    #[codec(compact)]
+    pub full_stake: Balance,
+    /// Maximum bonus on top of the base reward, e.g. 50% for a full stake, see
+    /// `base_reward`.
+    pub max_bonus: Perbill,
+    /// Number of blocks, for which unbonded tokens stay held, e.g. to be slashed.
+    pub unbonding_period: BlockNumber,
+}
+
+impl<Balance, BlockNumber> StakingConfig<Balance, BlockNumber>
+where
+    Balance: BalanceT,
+{
+    /// Method checks whether the configuration is consistent, i.e. a bonus can only be
+    /// given, if the full stake is defined.
+    pub fn is_consistent(&self) -> bool {
+        self.max_bonus.is_zero() || !self.full_stake.is_zero()
+    }
+
+    /// Method returns the online reward of machines without stake. It leaves room for the
+    /// maximum bonus, so fully staked machines earn the whole period reward, but not more.
+    pub fn base_reward(&self, period_reward: Balance) -> Balance {
+        if self.full_stake.is_zero() {
+            return period_reward;
+        }
+        let parts = Perbill::ACCURACY.saturating_add(self.max_bonus.deconstruct());
+        Perbill::from_rational(Perbill::ACCURACY, parts) * period_reward
+    }
+
+    /// Method computes the bonus on top of the given online reward for the given stake.
+    pub fn bonus(&self, reward: Balance, stake: Balance) -> Balance {
+        if self.full_stake.is_zero() {
+            return Balance::zero();
+        }
+        let ratio = Perbill::from_rational(stake.min(self.full_stake), self.full_stake);
+        self.max_bonus * (self.curve.apply(ratio) * reward)
+    }
+}
+
+/// This struct records, which one-time payments have been made for a machine. It outlives
+/// the machine's registration, so re-registering a removed machine cannot repeat them.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MachineTombstone {
+    /// The registration reward has been paid for the machine.
+    pub registration_rewarded: bool,
+    /// A bounty has been paid for reaping the machine's stale registration.
+    pub bounty_paid: bool,
+}
+
+/// This struct defines the tokens, which have been bonded behind a machine by its owner.
+/// Bonded tokens are held on the owner's account, see `HoldReason::MachineBond`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MachineBond<AccountId, Balance, BlockNumber> {
+    /// Owner, who has bonded the tokens.
+    pub owner: AccountId,
+    /// Bonded tokens, which weight the machine's online rewards.
+    #[codec(compact)]
+    pub active: Balance,
+    /// Unbonded tokens, which stay held until the end of the unbonding period.
+    #[codec(compact)]
+    pub unbonding: Balance,
+    /// Block, from which on the unbonded tokens can be withdrawn.
+    pub unlock_at: BlockNumber,
+}
+
+/// Evidence of a misbehaviour, e.g. a hash of spoofed heartbeats or a link to a proof.
+pub type Evidence = BoundedVec<u8, ConstU32<MAX_EVIDENCE_LEN>>;
+
+/// This struct defines a report of a machine's misbehaviour, which awaits adjudication
+/// by governance.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MisbehaviourReport<AccountId, BlockNumber> {
+    /// The reported machine.
+    pub machine: AccountId,
+    /// The reporter, e.g. a validator.
+    pub reporter: AccountId,
+    /// Evidence of the misbehaviour.
+    pub evidence: Evidence,
+    /// Block, in which the misbehaviour has been reported.
+    pub reported_at: BlockNumber,
+}
+
+/// This struct defines a confirmed offence of a machine, see `OffenceHistory`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Offence<Balance, BlockNumber> {
+    /// Block, in which the misbehaviour has been reported.
+    pub reported_at: BlockNumber,
+    /// Tokens, which have been slashed from the machine's bond.
+    #[codec(compact)]
+    pub slashed: Balance,
+    /// Block, until which the machine's online rewards are frozen.
+    pub frozen_until: BlockNumber,
+}
+
+/// This struct defines the last liveness of a machine, which the offchain workers of the
+/// designated reporters have agreed on, see `LivenessTally`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Liveness<BlockNumber> {
+    /// Whether the machine's endpoint has responded.
+    pub online: bool,
+    /// Block, in which the liveness has been attested.
+    pub attested_at: BlockNumber,
+}
+
+/// This struct counts the liveness attestations of a machine in a reward period. Each
+/// designated reporter has one attestation per machine and period, see `submit_liveness`.
+#[derive(
+    PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct LivenessTally {
+    /// Index of the reward period, in which the attestations have been submitted.
+    #[codec(compact)]
+    pub period: u32,
+    /// Number of reporters, which have attested the machine online.
+    #[codec(compact)]
+    pub online: u32,
+    /// Number of reporters, which have attested the machine offline.
+    #[codec(compact)]
+    pub offline: u32,
+}
+
+impl LivenessTally {
+    /// Method counts a reporter's attestation in the given period. The reporter's previous
+    /// attestation (period, online) will be replaced, if it has been made in the same period.
+    pub fn attest(&mut self, period: u32, previous: Option<(u32, bool)>, online: bool) {
+        if period != self.period {
+            *self = Self {
+                period,
+                ..Default::default()
+            };
+        }
+        match previous {
+            Some((p, true)) if p == period => self.online = self.online.saturating_sub(1),
+            Some((p, false)) if p == period => self.offline = self.offline.saturating_sub(1),
+            _ => {}
+        }
+        if online {
+            self.online = self.online.saturating_add(1);
+        } else {
+            self.offline = self.offline.saturating_add(1);
+        }
+    }
+
+    /// Method returns the liveness, which the reporters agree on: at least `threshold`
+    /// reporters and the majority of them. Returns None, if there is no agreement yet.
+    pub fn outcome(&self, threshold: u32) -> Option<bool> {
+        let threshold = threshold.max(1);
+        if self.online >= threshold && self.online > self.offline {
+            Some(true)
+        } else if self.offline >= threshold && self.offline > self.online {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// This struct defines the payload of a machine's heartbeat, which will be signed by the
+/// machine's account key, see `submit_heartbeat`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Heartbeat<AccountId> {
+    /// The machine, which sends the heartbeat.
+    pub machine: AccountId,
+    /// Index of the current reward period (block number divided by
+    /// `MorConfig::track_n_block_rewards`).
+    #[codec(compact)]
+    pub period: u32,
+    /// Number chosen by the machine, e.g. a counter, so its payloads differ.
+    #[codec(compact)]
+    pub nonce: u32,
+}
+
+impl<AccountId: Encode> Heartbeat<AccountId> {
+    /// Method returns the payload, which the machine signs: the heartbeat, prefixed by
+    /// `HEARTBEAT_CONTEXT` and the chain's genesis hash, so it cannot be replayed on other
+    /// chains or as another payload.
+    pub fn signing_payload<Hash: Encode>(&self, genesis_hash: &Hash) -> Vec<u8> {
+        (HEARTBEAT_CONTEXT, genesis_hash, self).encode()
+    }
+}
+
+/// This struct defines the lifetime statistics of a machine's or an owner's activity, i.e.
+/// its rewards and usage payments. Amounts are denominated in the native currency, rewards
+/// and payments in assets count by their native equivalent, see `AssetConfig::to_native`.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ActivityStatistics<Balance, BlockNumber> {
+    /// Total rewards after registration: online rewards, campaign rewards and rewards,
+    /// which have been paid by other pallets.
+    #[codec(compact)]
+    pub online_rewards: Balance,
+    /// Number of claimed online and campaign rewards.
+    #[codec(compact)]
+    pub reward_claims: u32,
+    /// Total amount of usage payments.
+    #[codec(compact)]
+    pub usage_revenue: Balance,
+    /// Number of usage payments.
+    #[codec(compact)]
+    pub usage_payments: u32,
+    /// Block of the last reward or usage payment.
+    pub last_active_at: BlockNumber,
+}
+
+impl<Balance: BalanceT, BlockNumber> ActivityStatistics<Balance, BlockNumber> {
+    /// Method records a reward, which has been claimed by the owner or paid by another pallet.
+    pub fn note_reward(&mut self, amount: Balance, claimed: bool, now: BlockNumber) {
+        self.online_rewards = self.online_rewards.saturating_add(amount);
+        if claimed {
+            self.reward_claims = self.reward_claims.saturating_add(1);
+        }
+        self.last_active_at = now;
+    }
+
+    /// Method records a usage payment.
+    pub fn note_usage_payment(&mut self, amount: Balance, now: BlockNumber) {
+        self.usage_revenue = self.usage_revenue.saturating_add(amount);
+        self.usage_payments = self.usage_payments.saturating_add(1);
+        self.last_active_at = now;
+    }
+}
+
+/// This struct defines the lifetime statistics of a registered machine, see `MachineStats`.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MachineStatistics<Balance, BlockNumber> {
+    /// Block, in which the machine has been registered. It is zero for machines, which
+    /// have been registered before these statistics were introduced.
+    pub registered_at: BlockNumber,
+    /// Rewards and usage payments of the machine.
+    pub activity: ActivityStatistics<Balance, BlockNumber>,
+}
+
+/// Purpose, for which tokens will be minted, see `MorBalance::mint_to_account`.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MintReason {
+    /// Registration reward of a machine.
+    Registration,
+    /// Payment for using a machine.
+    Usage,
+}
+
+/// This struct defines the protocol-wide metrics of Peaq-MOR, see `MorMetricsStorage`.
+/// Amounts have been counted since storage version 4.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MorMetrics<Balance> {
+    /// Total amount, which has been minted for registration rewards.
+    #[codec(compact)]
+    pub minted_for_registration: Balance,
+    /// Total amount, which has been minted for machine usage payments.
+    #[codec(compact)]
+    pub minted_for_usage: Balance,
+    /// Total amount, which has been paid out of the pot as online rewards or claimed as
+    /// campaign rewards. Asset rewards are counted by their native equivalent.
+    #[codec(compact)]
+    pub paid_from_pot: Balance,
+    /// Number of currently registered machines.
+    #[codec(compact)]
+    pub registered_machines: u32,
+    /// Index of the reward period, to which the numbers of active machines refer.
+    #[codec(compact)]
+    pub period: u32,
+    /// Number of machines, which have been registered, rewarded or paid in that period.
+    #[codec(compact)]
+    pub active_machines: u32,
+    /// Number of active machines in the period before.
+    #[codec(compact)]
+    pub previous_active_machines: u32,
+}
+
+impl<Balance: BalanceT> MorMetrics<Balance> {
+    /// Method adds the minted amount to the total of the given reason.
+    pub fn note_minted(&mut self, amount: Balance, reason: MintReason) {
+        let total = match reason {
+            MintReason::Registration => &mut self.minted_for_registration,
+            MintReason::Usage => &mut self.minted_for_usage,
+        };
+        *total = total.saturating_add(amount);
+    }
+
+    /// Method counts a machine as active in the given period, each machine shall only be
+    /// counted once per period.
+    pub fn note_active(&mut self, period: u32) {
+        self.roll_to(period);
+        self.active_machines = self.active_machines.saturating_add(1);
+    }
+
+    /// Method moves the numbers of active machines to the given period. The current number
+    /// becomes the previous one, if the given period follows directly.
+    pub fn roll_to(&mut self, period: u32) {
+        if period != self.period {
+            self.previous_active_machines = if period == self.period.saturating_add(1) {
+                self.active_machines
+            } else {
+                0
+            };
+            self.active_machines = 0;
+            self.period = period;
         }
     }
 }
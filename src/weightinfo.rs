@@ -5,7 +5,30 @@ use frame_support::weights::Weight;
 pub trait WeightInfo {
     fn get_registration_reward() -> Weight;
     fn get_online_rewards() -> Weight;
+    fn get_online_rewards_in_asset() -> Weight;
     fn pay_machine_usage() -> Weight;
-    fn set_configuration() -> Weight;
+    fn set_configuration(t: u32) -> Weight;
     fn fetch_pot_balance() -> Weight;
+    fn set_machine_tariff() -> Weight;
+    fn set_accepted_asset() -> Weight;
+    fn pay_machine_usage_in_asset() -> Weight;
+    fn set_reward_asset() -> Weight;
+    fn withdraw_from_pot() -> Weight;
+    fn donate_to_pot() -> Weight;
+    fn sweep_pot() -> Weight;
+    fn log_block_rewards(t: u32) -> Weight;
+    fn create_campaign() -> Weight;
+    fn claim_campaign_reward() -> Weight;
+    fn close_campaign() -> Weight;
+    fn set_machine_metadata() -> Weight;
+    fn set_eligibility_rules() -> Weight;
+    fn reap_stale_machine(a: u32) -> Weight;
+    fn bond() -> Weight;
+    fn unbond() -> Weight;
+    fn withdraw_unbonded() -> Weight;
+    fn set_staking_config() -> Weight;
+    fn report_misbehaviour() -> Weight;
+    fn adjudicate_misbehaviour() -> Weight;
+    fn submit_liveness(a: u32) -> Weight;
+    fn submit_heartbeat() -> Weight;
 }
@@ -1,24 +1,23 @@
-
-//! Autogenerated weights for `peaq_pallet_mor`
+//! Weights for `peaq_pallet_mor`
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-11-28, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! CPU: `AMD Ryzen 5 5600H with Radeon Graphics`
-//! EXECUTION: Some(Native), WASM-EXECUTION: Compiled, CHAIN: Some("dev-local"), DB CACHE: 1024
+//! These weights are conservative placeholders, they have NOT been measured. Each extrinsic
+//! is charged the same flat execution time, its database reads and writes, and a proof
+//! size per storage read, see the constants below. The storage items, which each extrinsic
+//! reads and writes in the worst case, are listed above its function. Replace this file by
+//! the output of the benchmarks on reference hardware, before the pallet is used in
+//! production, e.g. with this command:
 
-// Executed Command:
 // ./target/release/peaq-node
 // benchmark
 // pallet
 // --chain=dev-local
-// --execution=native
+// --execution=wasm
 // --wasm-execution=compiled
 // --pallet=peaq_pallet_mor
 // --extrinsic=*
 // --steps=50
 // --repeat=20
-// --output=weight.rs
+// --output=weights.rs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -28,81 +27,333 @@
 use frame_support::{traits::Get, weights::Weight};
 use core::marker::PhantomData;
 
+/// Placeholder for the execution time of any extrinsic, apart from its database accesses
+/// (300 µs), including the verification of one signature.
+const BASE: u64 = 300_000_000;
+/// Placeholder for the execution time per item of a component, e.g. per attestation.
+const PER_ITEM: u64 = 25_000_000;
+/// Placeholder for the proof size of one storage read. The largest item of this pallet is
+/// the ring buffer of block-rewards with at most 4_083 bytes, items of Peaq-DID are assumed
+/// to be smaller.
+const PROOF_PER_READ: u64 = 5_000;
+
 /// Weight functions for `peaq_pallet_mor`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
 	/// Storage: PeaqDid OwnerStore (r:1 w:0)
-	/// Proof Skipped: PeaqDid OwnerStore (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PeaqMor MachineEligibilityRules (r:1 w:0)
+	/// Storage: PeaqDid AttributeStore (r:8 w:0)
 	/// Storage: PeaqMor MachineRegister (r:1 w:1)
-	/// Proof Skipped: PeaqMor MachineRegister (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PeaqMor MachineTombstones (r:1 w:1)
 	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
-	/// Proof Skipped: PeaqMor MorConfigStorage (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PeaqMor MintedInPeriod (r:1 w:1)
+	/// Storage: PeaqMor TotalEmission (r:1 w:1)
 	/// Storage: System Account (r:1 w:1)
-	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: PeaqMor MachineAccounts (r:0 w:1)
+	/// Storage: PeaqMor MachineStats (r:0 w:1)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
 	fn get_registration_reward() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `531`
-		//  Estimated: `3996`
-		// Minimum execution time: 144_875_000 picoseconds.
-		Weight::from_parts(146_187_000, 0)
-			.saturating_add(Weight::from_parts(0, 3996))
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(2))
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(17))
+			.saturating_add(T::DbWeight::get().reads(17))
+			.saturating_add(T::DbWeight::get().writes(8))
 	}
 	/// Storage: PeaqDid OwnerStore (r:1 w:0)
-	/// Proof Skipped: PeaqDid OwnerStore (max_values: None, max_size: None, mode: Measured)
 	/// Storage: PeaqMor MachineRegister (r:1 w:0)
-	/// Proof Skipped: PeaqMor MachineRegister (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PeaqMor MachineEligibilityRules (r:1 w:0)
+	/// Storage: PeaqDid AttributeStore (r:8 w:0)
+	/// Storage: PeaqMor RewardsFrozenUntil (r:1 w:0)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor MachineLiveness (r:1 w:0)
+	/// Storage: PeaqMor LastHeartbeat (r:1 w:0)
 	/// Storage: PeaqMor PeriodRewardStorage (r:1 w:0)
-	/// Proof Skipped: PeaqMor PeriodRewardStorage (max_values: Some(1), max_size: None, mode: Measured)
-	/// Storage: System Account (r:1 w:0)
-	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: PeaqMor MachineBonds (r:1 w:0)
+	/// Storage: PeaqMor StakingConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor RewardAsset (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PeaqMor PotIsLow (r:1 w:0)
+	/// Storage: PeaqMor MachineStats (r:1 w:1)
+	/// Storage: PeaqMor OwnerStats (r:1 w:1)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
 	fn get_online_rewards() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `622`
-		//  Estimated: `4087`
-		// Minimum execution time: 50_345_000 picoseconds.
-		Weight::from_parts(51_427_000, 0)
-			.saturating_add(Weight::from_parts(0, 4087))
-			.saturating_add(T::DbWeight::get().reads(4))
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(25))
+			.saturating_add(T::DbWeight::get().reads(25))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: PeaqDid OwnerStore (r:1 w:0)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor MachineEligibilityRules (r:1 w:0)
+	/// Storage: PeaqDid AttributeStore (r:8 w:0)
+	/// Storage: PeaqMor RewardsFrozenUntil (r:1 w:0)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor MachineLiveness (r:1 w:0)
+	/// Storage: PeaqMor LastHeartbeat (r:1 w:0)
+	/// Storage: PeaqMor PeriodRewardStorage (r:1 w:0)
+	/// Storage: PeaqMor MachineBonds (r:1 w:0)
+	/// Storage: PeaqMor StakingConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor RewardAsset (r:1 w:0)
+	/// Storage: PeaqMor AcceptedAssets (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: PeaqMor MachineStats (r:1 w:1)
+	/// Storage: PeaqMor OwnerStats (r:1 w:1)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
+	fn get_online_rewards_in_asset() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(27))
+			.saturating_add(T::DbWeight::get().reads(27))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
-	/// Proof Skipped: PeaqMor MorConfigStorage (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PeaqMor MachineTariffs (r:1 w:0)
+	/// Storage: PeaqMor MintedInPeriod (r:1 w:1)
+	/// Storage: PeaqMor TotalEmission (r:1 w:1)
 	/// Storage: System Account (r:1 w:1)
-	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor MachineStats (r:1 w:1)
+	/// Storage: PeaqMor OwnerStats (r:1 w:1)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
 	fn pay_machine_usage() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `198`
-		//  Estimated: `3593`
-		// Minimum execution time: 129_776_000 picoseconds.
-		Weight::from_parts(130_728_000, 0)
-			.saturating_add(Weight::from_parts(0, 3593))
-			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(9))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(6))
 	}
 	/// Storage: PeaqMor RewardsRecordStorage (r:1 w:1)
-	/// Proof Skipped: PeaqMor RewardsRecordStorage (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: PeaqMor MorConfigStorage (r:0 w:1)
-	/// Proof Skipped: PeaqMor MorConfigStorage (max_values: Some(1), max_size: None, mode: Measured)
-	fn set_configuration() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `3377`
-		//  Estimated: `4862`
-		// Minimum execution time: 138_553_000 picoseconds.
-		Weight::from_parts(139_795_000, 0)
-			.saturating_add(Weight::from_parts(0, 4862))
+	/// Storage: PeaqMor PeriodRewardStorage (r:0 w:1)
+	fn set_configuration(t: u32) -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(1))
+			.saturating_add(Weight::from_parts(PER_ITEM, 0).saturating_mul(t.into()))
 			.saturating_add(T::DbWeight::get().reads(1))
-			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: System Account (r:1 w:0)
-	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn fetch_pot_balance() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `52`
-		//  Estimated: `3593`
-		// Minimum execution time: 24_276_000 picoseconds.
-		Weight::from_parts(24_646_000, 0)
-			.saturating_add(Weight::from_parts(0, 3593))
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(1))
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	/// Storage: PeaqDid OwnerStore (r:1 w:0)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor MachineTariffs (r:0 w:1)
+	fn set_machine_tariff() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(3))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Assets Asset (r:1 w:0)
+	/// Storage: PeaqMor AcceptedAssets (r:0 w:1)
+	fn set_accepted_asset() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(1))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor AcceptedAssets (r:1 w:0)
+	/// Storage: PeaqMor MachineTariffs (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor MachineStats (r:1 w:1)
+	/// Storage: PeaqMor OwnerStats (r:1 w:1)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
+	fn pay_machine_usage_in_asset() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(11))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: PeaqMor AcceptedAssets (r:1 w:0)
+	/// Storage: PeaqMor RewardAsset (r:0 w:1)
+	fn set_reward_asset() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(1))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PeaqMor PotIsLow (r:1 w:0)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
+	fn withdraw_from_pot() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(5))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor RewardsRecordStorage (r:1 w:1)
+	/// Storage: PeaqMor PeriodRewardStorage (r:0 w:1)
+	/// Storage: PeaqMor PotIsLow (r:1 w:0)
+	fn donate_to_pot() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(5))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	fn sweep_pot() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(2))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor RewardsRecordStorage (r:1 w:1)
+	/// Storage: PeaqMor PeriodRewardStorage (r:0 w:1)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: PeaqMor PotIsLow (r:1 w:0)
+	fn log_block_rewards(t: u32) -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(4))
+			.saturating_add(Weight::from_parts(PER_ITEM, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: PeaqMor NextCampaignId (r:1 w:1)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PeaqMor PotIsLow (r:1 w:0)
+	/// Storage: PeaqMor Campaigns (r:0 w:1)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
+	fn create_campaign() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(6))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: PeaqDid OwnerStore (r:1 w:0)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor Campaigns (r:1 w:1)
+	/// Storage: PeaqMor MachineEligibilityRules (r:1 w:0)
+	/// Storage: PeaqDid AttributeStore (r:9 w:0)
+	/// Storage: PeaqMor RewardsFrozenUntil (r:1 w:0)
+	/// Storage: PeaqMor MachineLiveness (r:1 w:0)
+	/// Storage: PeaqMor LastHeartbeat (r:1 w:0)
+	/// Storage: PeaqMor CampaignClaims (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PeaqMor MachineStats (r:1 w:1)
+	/// Storage: PeaqMor OwnerStats (r:1 w:1)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
+	fn claim_campaign_reward() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(23))
+			.saturating_add(T::DbWeight::get().reads(23))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: PeaqMor Campaigns (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	fn close_campaign() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(3))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: PeaqDid OwnerStore (r:1 w:0)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqDid AttributeStore (r:1 w:0)
+	/// Storage: PeaqMor MachineMetadataOf (r:0 w:1)
+	fn set_machine_metadata() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(3))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor MachineEligibilityRules (r:0 w:1)
+	fn set_eligibility_rules() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor MachineRegister (r:1 w:1)
+	/// Storage: PeaqDid OwnerStore (r:2 w:0)
+	/// Storage: PeaqMor MachineAccounts (r:1 w:1)
+	/// Storage: PeaqMor MachineTariffs (r:0 w:1)
+	/// Storage: PeaqMor MachineMetadataOf (r:0 w:1)
+	/// Storage: PeaqMor MachineLiveness (r:0 w:1)
+	/// Storage: PeaqMor LivenessTallies (r:0 w:1)
+	/// Storage: PeaqMor LastHeartbeat (r:0 w:1)
+	/// Storage: PeaqMor FeelessCallsUsed (r:0 w:1)
+	/// Storage: PeaqMor MachineStats (r:0 w:1)
+	/// Storage: PeaqMor PendingReports (r:0 w:1)
+	/// Storage: PeaqMor OffenceHistory (r:0 w:1)
+	/// Storage: PeaqMor RewardsFrozenUntil (r:0 w:1)
+	/// Storage: PeaqMor MachineBonds (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: PeaqMor MachineTombstones (r:1 w:1)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: System Account (r:3 w:3)
+	/// Storage: PeaqMor PotIsLow (r:1 w:0)
+	/// Storage: PeaqMor MorMetricsStorage (r:1 w:1)
+	/// Storage: PeaqMor LivenessAttestations (r:0 w:1) per item of `a`
+	fn reap_stale_machine(a: u32) -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(13))
+			.saturating_add(Weight::from_parts(PER_ITEM, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(19))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+	}
+	/// Storage: PeaqDid OwnerStore (r:1 w:0)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor MachineBonds (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn bond() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(5))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: PeaqMor MachineBonds (r:1 w:1)
+	/// Storage: PeaqMor StakingConfigStorage (r:1 w:0)
+	fn unbond() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(2))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor MachineBonds (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	fn withdraw_unbonded() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(3))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: PeaqMor StakingConfigStorage (r:0 w:1)
+	fn set_staking_config() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(0))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor PendingReports (r:1 w:1)
+	fn report_misbehaviour() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(2))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: PeaqMor PendingReports (r:1 w:1)
+	/// Storage: PeaqMor MachineBonds (r:1 w:1)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor OffenceHistory (r:1 w:1)
+	/// Storage: PeaqMor RewardsFrozenUntil (r:0 w:1)
+	fn adjudicate_misbehaviour() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(7))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor MachineRegister (r:1 w:0) per item of `a`
+	/// Storage: PeaqMor LivenessAttestations (r:1 w:1) per item of `a`
+	/// Storage: PeaqMor LivenessTallies (r:1 w:1) per item of `a`
+	/// Storage: PeaqMor MachineLiveness (r:1 w:1) per item of `a`
+	fn submit_liveness(a: u32) -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(1))
+			.saturating_add(Weight::from_parts(PER_ITEM, 0).saturating_mul(a.into()))
 			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, PROOF_PER_READ.saturating_mul(4)).saturating_mul(a.into()))
+	}
+	/// Storage: PeaqMor MachineRegister (r:1 w:0)
+	/// Storage: PeaqMor MorConfigStorage (r:1 w:0)
+	/// Storage: PeaqMor LastHeartbeat (r:1 w:1)
+	/// Storage: System BlockHash (r:1 w:0)
+	fn submit_heartbeat() -> Weight {
+		Weight::from_parts(BASE, PROOF_PER_READ.saturating_mul(4))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
 	}
 }